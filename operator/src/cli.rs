@@ -1,5 +1,12 @@
 // This file is generated by kopgen. Do not edit manually. If you need to make adjustments add it to .openapi-generator-ignore file.
-use clap::{Parser, Subcommand};
+use std::{net::SocketAddr, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    config::{AccessToken, OperatorConfig},
+    errors::OperatorError,
+};
 
 /// Command-line interface for the operator.
 #[derive(Parser)]
@@ -37,8 +44,290 @@ pub enum Commands {
             help = "Install CRDs before running the operator"
         )]
         install_crds: bool,
+
+        /// Overrides of the configuration file
+        #[command(flatten)]
+        config: Box<ConfigOverrides>,
+
+        /// Option to reload the configuration file when it changes
+        #[arg(
+            long,
+            env = "WATCH_CONFIG",
+            help = "Reload the configuration file when it changes, e.g. after the ConfigMap was updated"
+        )]
+        watch_config: bool,
+
+        /// Option to enable leader election
+        #[arg(
+            long,
+            env = "LEADER_ELECTION",
+            help = "Enable leader election so only one replica runs the controllers"
+        )]
+        leader_election: bool,
+
+        /// The name of the Lease used for leader election
+        #[arg(
+            long,
+            env = "LEADER_ELECTION_LEASE_NAME",
+            default_value = "operator-leader",
+            help = "The name of the Lease used for leader election"
+        )]
+        leader_election_lease_name: String,
+
+        /// The namespace of the Lease used for leader election
+        #[arg(
+            long,
+            env = "POD_NAMESPACE",
+            default_value = "default",
+            help = "The namespace of the Lease used for leader election"
+        )]
+        leader_election_namespace: String,
+
+        /// The namespace the operator runs in
+        #[arg(
+            long,
+            env = "POD_NAMESPACE",
+            default_value = "default",
+            help = "The namespace the operator runs in, which holds the record of the released remote resources"
+        )]
+        operator_namespace: String,
+
+        /// The identity of this replica in the leader election
+        #[arg(
+            long,
+            env = "POD_NAME",
+            help = "The identity of this replica in the leader election, defaults to a random ID"
+        )]
+        leader_election_identity: Option<String>,
+
+        /// How long the Lease is valid without being renewed
+        #[arg(
+            long,
+            env = "LEADER_ELECTION_LEASE_DURATION",
+            default_value_t = 15,
+            help = "How long in seconds the Lease is valid without being renewed"
+        )]
+        leader_election_lease_duration: u64,
+
+        /// Option to make readiness depend on the remote API
+        #[arg(
+            long,
+            env = "CHECK_REMOTE_API",
+            help = "Report the operator as not ready while the remote API is unreachable"
+        )]
+        check_remote_api: bool,
+
+        /// How long running reconciliations may take to finish on shutdown
+        #[arg(
+            long,
+            env = "SHUTDOWN_GRACE_PERIOD",
+            default_value_t = 25,
+            help = "How long in seconds running reconciliations may take to finish on shutdown"
+        )]
+        shutdown_grace_period: u64,
     },
     /// Displays the CLI version.
     #[command(about = "Show the CLI version")]
     Version,
 }
+
+/// Options of the `run` command that override the configuration file.
+#[derive(Args, Default)]
+pub struct ConfigOverrides {
+    /// Path to the configuration file
+    #[arg(
+        long = "config",
+        env = "OPERATOR_CONFIG",
+        help = "Path to the YAML configuration file, defaults are used when omitted"
+    )]
+    pub config_file: Option<PathBuf>,
+
+    /// Option to watch resources in all namespaces
+    #[arg(
+        long,
+        env = "WATCH_ALL_NAMESPACES",
+        help = "Watch resources in all namespaces, takes precedence over --namespaces"
+    )]
+    pub all_namespaces: bool,
+
+    /// Namespaces to watch for resources
+    #[arg(
+        long,
+        env = "WATCH_NAMESPACES",
+        value_delimiter = ',',
+        help = "Comma separated list of namespaces to watch for resources"
+    )]
+    pub namespaces: Vec<String>,
+
+    /// The interval between two reconciliations of a healthy resource
+    #[arg(
+        long,
+        env = "REQUEUE_INTERVAL",
+        help = "The interval in seconds between two reconciliations of a healthy resource"
+    )]
+    pub requeue_interval: Option<u64>,
+
+    /// How many cats are reconciled at the same time
+    #[arg(
+        long,
+        env = "CATS_CONCURRENCY",
+        help = "How many cats are reconciled at the same time, 0 means unlimited"
+    )]
+    pub cats_concurrency: Option<u16>,
+
+    /// How many dogs are reconciled at the same time
+    #[arg(
+        long,
+        env = "DOGS_CONCURRENCY",
+        help = "How many dogs are reconciled at the same time, 0 means unlimited"
+    )]
+    pub dogs_concurrency: Option<u16>,
+
+    /// How many horses are reconciled at the same time
+    #[arg(
+        long,
+        env = "HORSES_CONCURRENCY",
+        help = "How many horses are reconciled at the same time, 0 means unlimited"
+    )]
+    pub horses_concurrency: Option<u16>,
+
+    /// The address of the health and metrics server
+    #[arg(
+        long,
+        env = "BIND_ADDRESS",
+        help = "The address the health and metrics server listens on, e.g. 0.0.0.0:8000"
+    )]
+    pub bind_address: Option<SocketAddr>,
+
+    /// The URL of the remote API
+    #[arg(long, env = "API_URL", help = "The URL of the remote API")]
+    pub api_url: Option<String>,
+
+    /// The user agent sent to the remote API
+    #[arg(
+        long,
+        env = "API_USER_AGENT",
+        help = "The user agent sent to the remote API"
+    )]
+    pub api_user_agent: Option<String>,
+
+    /// The access token of the remote API
+    #[arg(
+        long,
+        env = "ACCESS_TOKEN",
+        hide_env_values = true,
+        help = "The access token of the remote API, ignored when an access token file is set"
+    )]
+    pub access_token: Option<String>,
+
+    /// The file the access token of the remote API is read from
+    #[arg(
+        long,
+        env = "ACCESS_TOKEN_FILE",
+        help = "The file the access token of the remote API is read from, e.g. a mounted secret"
+    )]
+    pub access_token_file: Option<PathBuf>,
+
+    /// The timeout of requests to the remote API
+    #[arg(
+        long,
+        env = "API_TIMEOUT",
+        help = "The timeout in seconds of requests to the remote API"
+    )]
+    pub api_timeout: Option<u64>,
+
+    /// The timeout of connecting to the remote API
+    #[arg(
+        long,
+        env = "API_CONNECT_TIMEOUT",
+        help = "The timeout in seconds of connecting to the remote API"
+    )]
+    pub api_connect_timeout: Option<u64>,
+}
+
+impl ConfigOverrides {
+    /// Loads the configuration file and applies the overrides given on the command line
+    /// or through the environment, then validates the result.
+    pub fn load(&self) -> Result<OperatorConfig, OperatorError> {
+        let mut config = OperatorConfig::load(self.config_file.as_deref())?;
+        self.apply(&mut config);
+        config.read_access_token()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Applies the overrides that were given to the configuration.
+    pub fn apply(&self, config: &mut OperatorConfig) {
+        if self.all_namespaces {
+            config.watch.all_namespaces = true;
+        }
+        if !self.namespaces.is_empty() {
+            config.watch.namespaces = self.namespaces.clone();
+        }
+        if let Some(requeue_interval) = self.requeue_interval {
+            config.requeue.interval_seconds = requeue_interval;
+        }
+        if let Some(concurrency) = self.cats_concurrency {
+            config.controllers.cats.concurrency = concurrency;
+        }
+        if let Some(concurrency) = self.dogs_concurrency {
+            config.controllers.dogs.concurrency = concurrency;
+        }
+        if let Some(concurrency) = self.horses_concurrency {
+            config.controllers.horses.concurrency = concurrency;
+        }
+        if let Some(bind_address) = self.bind_address {
+            config.server.bind_address = bind_address;
+        }
+        if let Some(api_url) = &self.api_url {
+            config.remote_api.url = api_url.clone();
+        }
+        if let Some(api_user_agent) = &self.api_user_agent {
+            config.remote_api.user_agent = api_user_agent.clone();
+        }
+        if let Some(access_token) = &self.access_token {
+            config.remote_api.access_token = Some(AccessToken(access_token.clone()));
+        }
+        if let Some(access_token_file) = &self.access_token_file {
+            config.remote_api.access_token_file = Some(access_token_file.clone());
+        }
+        if let Some(api_timeout) = self.api_timeout {
+            config.remote_api.timeout_seconds = api_timeout;
+        }
+        if let Some(api_connect_timeout) = self.api_connect_timeout {
+            config.remote_api.connect_timeout_seconds = api_connect_timeout;
+        }
+    }
+}
+
+/// The namespaces the operator watches for resources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchScope {
+    /// Watch resources across the whole cluster.
+    AllNamespaces,
+    /// Watch resources only in the listed namespaces.
+    Namespaces(Vec<String>),
+}
+
+impl WatchScope {
+    /// Builds the watch scope from the `run` command options.
+    /// Falls back to the `default` namespace when no namespace is given.
+    pub fn new(all_namespaces: bool, namespaces: &[String]) -> Self {
+        if all_namespaces {
+            return WatchScope::AllNamespaces;
+        }
+
+        let mut unique_namespaces: Vec<String> = Vec::new();
+        for namespace in namespaces.iter().map(|namespace| namespace.trim()) {
+            if !namespace.is_empty() && !unique_namespaces.iter().any(|n| n == namespace) {
+                unique_namespaces.push(namespace.to_string());
+            }
+        }
+
+        if unique_namespaces.is_empty() {
+            unique_namespaces.push("default".to_string());
+        }
+
+        WatchScope::Namespaces(unique_namespaces)
+    }
+}
@@ -0,0 +1,460 @@
+use std::{
+    fmt,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use log::LevelFilter;
+
+use serde::Deserialize;
+
+use crate::errors::OperatorError;
+
+/// The configuration of the operator, read from a YAML file.
+/// Every field is optional in the file and falls back to its default.
+///
+/// ```yaml
+/// logLevel: info
+/// requeue:
+///   intervalSeconds: 30
+///   backoffBaseSeconds: 5
+///   backoffMaxSeconds: 300
+/// controllers:
+///   cats:
+///     concurrency: 4
+/// watch:
+///   allNamespaces: false
+///   namespaces: [default]
+/// server:
+///   bindAddress: 0.0.0.0:8000
+/// remoteApi:
+///   url: http://fake-server:8080
+///   userAgent: operator
+///   timeoutSeconds: 30
+///   connectTimeoutSeconds: 5
+///   accessTokenFile: /etc/operator-secret/ACCESS_TOKEN
+/// import:
+///   enabled: false
+///   namespace: default
+///   intervalSeconds: 300
+/// orphans:
+///   enabled: false
+///   policy: report
+///   intervalSeconds: 600
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct OperatorConfig {
+    /// The log level, e.g. `debug`. Takes precedence over `RUST_LOG` when set.
+    pub log_level: Option<String>,
+    pub requeue: RequeueConfig,
+    pub controllers: ControllersConfig,
+    pub watch: WatchConfig,
+    pub server: ServerConfig,
+    pub remote_api: RemoteApiConfig,
+    pub import: ImportConfig,
+    pub orphans: OrphansConfig,
+}
+
+/// When resources are reconciled again.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct RequeueConfig {
+    /// The interval between two reconciliations of a healthy resource.
+    pub interval_seconds: u64,
+    /// The delay before the first retry of a failed resource.
+    pub backoff_base_seconds: u64,
+    /// The longest delay between two retries of a failed resource.
+    pub backoff_max_seconds: u64,
+}
+
+impl Default for RequeueConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 30,
+            backoff_base_seconds: 5,
+            backoff_max_seconds: 300,
+        }
+    }
+}
+
+/// The settings of every controller.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ControllersConfig {
+    pub cats: ControllerConfig,
+    pub dogs: ControllerConfig,
+    pub horses: ControllerConfig,
+}
+
+/// The settings of a single controller.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ControllerConfig {
+    /// How many resources are reconciled at the same time, 0 means unlimited.
+    pub concurrency: u16,
+}
+
+/// Which namespaces are watched for resources.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct WatchConfig {
+    pub all_namespaces: bool,
+    pub namespaces: Vec<String>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            all_namespaces: false,
+            namespaces: vec!["default".to_string()],
+        }
+    }
+}
+
+/// The HTTP server exposing the health probes and the metrics.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind_address: SocketAddr,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([0, 0, 0, 0], 8000)),
+        }
+    }
+}
+
+/// The connection to the remote API. The access token is not part of the file,
+/// it is read from the operator secret, either from a mounted file or the environment.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct RemoteApiConfig {
+    pub url: String,
+    pub user_agent: String,
+    pub timeout_seconds: u64,
+    pub connect_timeout_seconds: u64,
+    /// The file the access token is read from, e.g. a mounted secret.
+    pub access_token_file: Option<PathBuf>,
+    /// The access token, read from the access token file or the environment.
+    #[serde(skip)]
+    pub access_token: Option<AccessToken>,
+}
+
+/// An access token that is never printed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccessToken(pub String);
+
+impl fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AccessToken(***)")
+    }
+}
+
+impl Default for RemoteApiConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8080".to_string(),
+            user_agent: "operator".to_string(),
+            timeout_seconds: 30,
+            connect_timeout_seconds: 5,
+            access_token_file: None,
+            access_token: None,
+        }
+    }
+}
+
+/// Importing remote resources that no custom resource references, so the cluster
+/// holds the full inventory of the remote API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ImportConfig {
+    pub enabled: bool,
+    /// The namespace the imported resources are created in, it must be watched.
+    pub namespace: String,
+    /// The interval between two imports. A remote resource is imported once two
+    /// consecutive imports found it unreferenced.
+    pub interval_seconds: u64,
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            namespace: "default".to_string(),
+            interval_seconds: 300,
+        }
+    }
+}
+
+/// Sweeping the remote resources that no custom resource references, e.g. because the
+/// operator stopped between creating a remote resource and recording its ID.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct OrphansConfig {
+    pub enabled: bool,
+    pub policy: OrphanPolicy,
+    /// The interval between two sweeps.
+    pub interval_seconds: u64,
+}
+
+impl Default for OrphansConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            policy: OrphanPolicy::Report,
+            interval_seconds: 600,
+        }
+    }
+}
+
+/// What happens to an orphaned remote resource.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanPolicy {
+    /// Only reports the orphan.
+    #[default]
+    Report,
+    /// Records the orphan as released by the operator, leaving the remote resource untouched,
+    /// so it is reported once and neither imported nor swept again.
+    Tag,
+    /// Deletes the orphan from the remote API.
+    Delete,
+}
+
+impl OrphanPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrphanPolicy::Report => "report",
+            OrphanPolicy::Tag => "tag",
+            OrphanPolicy::Delete => "delete",
+        }
+    }
+}
+
+impl OperatorConfig {
+    /// Reads the configuration from a YAML file, or returns the defaults when no file is given.
+    pub fn load(path: Option<&Path>) -> Result<Self, OperatorError> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            OperatorError::InvalidConfig(format!("failed to read `{}`: {}", path.display(), e))
+        })?;
+        Self::parse(&content).map_err(|e| {
+            OperatorError::InvalidConfig(format!("failed to parse `{}`: {}", path.display(), e))
+        })
+    }
+
+    /// Parses the configuration from YAML. An empty document yields the defaults.
+    pub fn from_yaml(content: &str) -> Result<Self, OperatorError> {
+        Self::parse(content).map_err(|e| OperatorError::InvalidConfig(e.to_string()))
+    }
+
+    fn parse(content: &str) -> Result<Self, serde_yaml::Error> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yaml::from_str(content)
+    }
+
+    /// Reads the access token from the access token file, if one is configured.
+    pub fn read_access_token(&mut self) -> Result<(), OperatorError> {
+        let Some(path) = &self.remote_api.access_token_file else {
+            return Ok(());
+        };
+
+        let token = std::fs::read_to_string(path).map_err(|e| {
+            OperatorError::InvalidConfig(format!(
+                "failed to read the access token from `{}`: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.remote_api.access_token = Some(AccessToken(token.trim().to_string()));
+        Ok(())
+    }
+
+    /// Returns the configured log level, if any.
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        self.log_level
+            .as_deref()
+            .and_then(|level| level.parse().ok())
+    }
+
+    /// Checks that the configuration can be used, listing every problem found.
+    pub fn validate(&self) -> Result<(), OperatorError> {
+        let mut problems = Vec::new();
+
+        if let Some(level) = &self.log_level {
+            if level.parse::<LevelFilter>().is_err() {
+                problems.push(format!(
+                    "logLevel `{}` must be one of off, error, warn, info, debug, trace",
+                    level
+                ));
+            }
+        }
+
+        if self.requeue.interval_seconds == 0 {
+            problems.push("requeue.intervalSeconds must be greater than 0".to_string());
+        }
+        if self.requeue.backoff_base_seconds == 0 {
+            problems.push("requeue.backoffBaseSeconds must be greater than 0".to_string());
+        }
+        if self.requeue.backoff_max_seconds < self.requeue.backoff_base_seconds {
+            problems.push(
+                "requeue.backoffMaxSeconds must not be less than requeue.backoffBaseSeconds"
+                    .to_string(),
+            );
+        }
+
+        if !self.watch.all_namespaces {
+            if self.watch.namespaces.is_empty() {
+                problems.push(
+                    "watch.namespaces must not be empty unless watch.allNamespaces is set"
+                        .to_string(),
+                );
+            }
+            for namespace in &self.watch.namespaces {
+                if !is_valid_namespace(namespace.trim()) {
+                    problems.push(format!(
+                        "watch.namespaces contains an invalid namespace `{}`",
+                        namespace
+                    ));
+                }
+            }
+        }
+
+        if reqwest::Url::parse(&self.remote_api.url).is_err() {
+            problems.push(format!(
+                "remoteApi.url `{}` is not a valid URL",
+                self.remote_api.url
+            ));
+        }
+        if self.remote_api.timeout_seconds == 0 {
+            problems.push("remoteApi.timeoutSeconds must be greater than 0".to_string());
+        }
+        if self.remote_api.connect_timeout_seconds == 0 {
+            problems.push("remoteApi.connectTimeoutSeconds must be greater than 0".to_string());
+        }
+
+        if self.import.enabled {
+            if !is_valid_namespace(&self.import.namespace) {
+                problems.push(format!(
+                    "import.namespace `{}` is not a valid namespace",
+                    self.import.namespace
+                ));
+            } else if !self.watch.all_namespaces
+                && !self
+                    .watch
+                    .namespaces
+                    .iter()
+                    .any(|namespace| namespace.trim() == self.import.namespace)
+            {
+                problems.push(format!(
+                    "import.namespace `{}` must be one of the watched namespaces",
+                    self.import.namespace
+                ));
+            }
+            if self.import.interval_seconds == 0 {
+                problems.push("import.intervalSeconds must be greater than 0".to_string());
+            }
+        }
+
+        if self.orphans.enabled {
+            if self.orphans.interval_seconds == 0 {
+                problems.push("orphans.intervalSeconds must be greater than 0".to_string());
+            }
+            if self.import.enabled {
+                problems.push(
+                    "orphans and import must not both be enabled, the import adopts the orphans"
+                        .to_string(),
+                );
+            }
+            if self.orphans.policy == OrphanPolicy::Delete && !self.watch.all_namespaces {
+                problems.push(
+                    "orphans.policy delete requires watch.allNamespaces, otherwise the remote resources of unwatched namespaces would be deleted"
+                        .to_string(),
+                );
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(OperatorError::InvalidConfig(problems.join("; ")))
+        }
+    }
+
+    /// Lists the sections that differ from `other` and only take effect after a restart,
+    /// i.e. everything except the log level, the requeue interval and the remote API.
+    pub fn restart_required_changes(&self, other: &Self) -> Vec<&'static str> {
+        let mut changes = Vec::new();
+        if self.requeue.backoff_base_seconds != other.requeue.backoff_base_seconds
+            || self.requeue.backoff_max_seconds != other.requeue.backoff_max_seconds
+        {
+            changes.push("requeue.backoff");
+        }
+        if self.controllers != other.controllers {
+            changes.push("controllers");
+        }
+        if self.watch != other.watch {
+            changes.push("watch");
+        }
+        if self.server != other.server {
+            changes.push("server");
+        }
+        if self.import != other.import {
+            changes.push("import");
+        }
+        if self.orphans != other.orphans {
+            changes.push("orphans");
+        }
+        changes
+    }
+
+    /// Returns the settings a controller runs with.
+    pub fn controller_settings(&self, controller: &ControllerConfig) -> ControllerSettings {
+        ControllerSettings {
+            requeue_after: Duration::from_secs(self.requeue.interval_seconds),
+            backoff_base: Duration::from_secs(self.requeue.backoff_base_seconds),
+            backoff_max: Duration::from_secs(self.requeue.backoff_max_seconds),
+            concurrency: controller.concurrency,
+        }
+    }
+}
+
+/// The tunables a controller runs with, derived from the [`OperatorConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControllerSettings {
+    /// The interval between two reconciliations of a healthy resource.
+    pub requeue_after: Duration,
+    /// The delay before the first retry of a failed resource.
+    pub backoff_base: Duration,
+    /// The longest delay between two retries of a failed resource.
+    pub backoff_max: Duration,
+    /// How many resources are reconciled at the same time, 0 means unlimited.
+    pub concurrency: u16,
+}
+
+impl Default for ControllerSettings {
+    fn default() -> Self {
+        let config = OperatorConfig::default();
+        config.controller_settings(&ControllerConfig::default())
+    }
+}
+
+/// Checks whether the name is a valid namespace, i.e. a DNS label.
+fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace.len() <= 63
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !namespace.starts_with('-')
+        && !namespace.ends_with('-')
+}
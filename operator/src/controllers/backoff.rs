@@ -0,0 +1,72 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use rand::Rng;
+
+/// Tracks consecutive failures per resource and computes how long to wait before retrying,
+/// so a resource that keeps failing is retried less and less often while the others
+/// keep their normal cadence.
+///
+/// The delay doubles with every failure up to a cap, with jitter so that resources
+/// failing at the same time don't retry at the same time.
+#[derive(Debug)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    failures: Mutex<HashMap<String, u32>>,
+}
+
+impl Backoff {
+    /// Creates a new backoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The delay before the first retry.
+    /// * `max` - The longest delay between two retries.
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a failure of the resource and returns how long to wait before retrying it.
+    /// The delay is a random duration between half and all of the exponential delay.
+    pub fn next_delay(&self, key: &str) -> Duration {
+        let failures = {
+            let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+            let count = failures.entry(key.to_string()).or_default();
+            *count = count.saturating_add(1);
+            *count
+        };
+
+        let delay = self.delay_for(failures);
+        rand::thread_rng().gen_range(delay / 2..=delay)
+    }
+
+    /// Returns the number of consecutive failures of the resource.
+    pub fn failures(&self, key: &str) -> u32 {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Forgets the failures of the resource, e.g. after it has been reconciled successfully.
+    pub fn reset(&self, key: &str) {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+
+    /// Returns the capped exponential delay, without jitter, after the given number of failures.
+    fn delay_for(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        self.base
+            .checked_mul(1 << exponent)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::Utc;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+
+use crate::{
+    controllers::reconciler::SyncedResource, errors::OperatorError, types::status::ResourceStatus,
+};
+
+/// The condition summarizing whether the remote resource is synced and healthy,
+/// so `kubectl wait --for=condition=Ready` works for every kind.
+pub const READY_CONDITION: &str = "Ready";
+
+/// The condition reporting whether the spec of the current generation was applied
+/// to the remote resource.
+pub const SYNCED_CONDITION: &str = "Synced";
+
+/// The condition reporting whether the remote resource differs from the spec.
+pub const DRIFTED_CONDITION: &str = "Drifted";
+
+/// The condition reporting whether the last reconciliation failed.
+pub const ERROR_CONDITION: &str = "Error";
+
+/// The condition set on resources that failed permanently.
+pub const STALLED_CONDITION: &str = "Stalled";
+
+/// The condition set on resources whose remote resource no longer exists and is not recreated.
+pub const LOST_CONDITION: &str = "Lost";
+
+/// The condition reporting whether the reconciliation of the resource is suspended.
+pub const SUSPENDED_CONDITION: &str = "Suspended";
+
+/// The conditions set by earlier versions of the operator, replaced by the ones above.
+const LEGACY_CONDITIONS: [&str; 5] = [
+    "AvailableCreated",
+    "AvailableUpdated",
+    "AvailableRecreated",
+    "AvailableAdopted",
+    "AvailableFailed",
+];
+
+/// The status of a condition, following the Kubernetes API conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionStatus {
+    True,
+    False,
+    Unknown,
+}
+
+impl ConditionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionStatus::True => "True",
+            ConditionStatus::False => "False",
+            ConditionStatus::Unknown => "Unknown",
+        }
+    }
+}
+
+impl From<bool> for ConditionStatus {
+    fn from(value: bool) -> Self {
+        if value {
+            ConditionStatus::True
+        } else {
+            ConditionStatus::False
+        }
+    }
+}
+
+/// Returns the condition of the given type, if the resource has one.
+pub fn find<'a, T>(resource: &'a T, type_: &str) -> Option<&'a Condition>
+where
+    T: SyncedResource,
+{
+    resource.status().and_then(|status| status.condition(type_))
+}
+
+/// Checks whether the condition of the given type is `True`.
+pub fn is_true<T>(resource: &T, type_: &str) -> bool
+where
+    T: SyncedResource,
+{
+    find(resource, type_).is_some_and(|condition| condition.status == "True")
+}
+
+/// Sets the condition of the given type and derives the `Ready` condition again.
+pub fn set<T>(
+    resource: &mut T,
+    type_: &str,
+    status: impl Into<ConditionStatus>,
+    reason: &str,
+    message: String,
+) where
+    T: SyncedResource,
+{
+    let generation = resource.meta().generation;
+    let Some(resource_status) = resource.status_mut().as_mut() else {
+        return;
+    };
+    resource_status.set_condition(condition(type_, status.into(), reason, message, generation));
+
+    let (ready, reason, message) = ready(resource_status.conditions());
+    resource_status.set_condition(condition(
+        READY_CONDITION,
+        ready,
+        reason,
+        message,
+        generation,
+    ));
+}
+
+/// Sets the `Error` condition to the error.
+pub fn set_error<T>(resource: &mut T, error: &OperatorError)
+where
+    T: SyncedResource,
+{
+    set(
+        resource,
+        ERROR_CONDITION,
+        true,
+        error.variant_name(),
+        error.to_string(),
+    );
+}
+
+/// Removes the conditions set by earlier versions of the operator.
+pub fn remove_legacy<T>(resource: &mut T)
+where
+    T: SyncedResource,
+{
+    if let Some(status) = resource.status_mut().as_mut() {
+        for type_ in LEGACY_CONDITIONS {
+            status.remove_condition(type_);
+        }
+    }
+}
+
+/// Derives the `Ready` condition from the other conditions: the resource is ready once the
+/// remote resource has been synced, unless the reconciliation is suspended or failing,
+/// or the remote resource has drifted or is lost.
+fn ready(conditions: &[Condition]) -> (ConditionStatus, &'static str, String) {
+    let with_status = |type_: &str, status: &str| {
+        conditions
+            .iter()
+            .find(|condition| condition.type_ == type_ && condition.status == status)
+    };
+
+    if with_status(SUSPENDED_CONDITION, "True").is_some() {
+        return (
+            ConditionStatus::False,
+            "Suspended",
+            "The reconciliation is suspended".to_string(),
+        );
+    }
+    if let Some(stalled) = with_status(STALLED_CONDITION, "True") {
+        return (ConditionStatus::False, "Stalled", stalled.message.clone());
+    }
+    if let Some(error) = with_status(ERROR_CONDITION, "True") {
+        return (ConditionStatus::False, "Error", error.message.clone());
+    }
+    if let Some(lost) = with_status(LOST_CONDITION, "True") {
+        return (ConditionStatus::False, "RemoteLost", lost.message.clone());
+    }
+    if let Some(drifted) = with_status(DRIFTED_CONDITION, "True") {
+        return (ConditionStatus::False, "Drifted", drifted.message.clone());
+    }
+    if let Some(synced) = with_status(SYNCED_CONDITION, "True") {
+        return (ConditionStatus::True, "Synced", synced.message.clone());
+    }
+    if let Some(not_synced) = with_status(SYNCED_CONDITION, "False") {
+        return (
+            ConditionStatus::False,
+            "NotSynced",
+            not_synced.message.clone(),
+        );
+    }
+    (
+        ConditionStatus::Unknown,
+        "Reconciling",
+        "The resource has not been synced yet".to_string(),
+    )
+}
+
+fn condition(
+    type_: &str,
+    status: ConditionStatus,
+    reason: &str,
+    message: String,
+    generation: Option<i64>,
+) -> Condition {
+    Condition {
+        status: status.as_str().to_string(),
+        type_: type_.to_string(),
+        message,
+        reason: reason.to_string(),
+        last_transition_time: Time(Utc::now()),
+        observed_generation: generation,
+    }
+}
@@ -0,0 +1,117 @@
+// This file is generated by kopgen. Do not edit manually. If you need to make adjustments add it to .openapi-generator-ignore file.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use openapi::{apis::dogs_api::DogsApi, models::Dog as DogDto};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::ControllerSettings,
+    controllers::reconciler::{self, RemoteResource},
+    errors::{OperatorError, RemoteApiError},
+    metrics::METRICS,
+    reload::Reloadable,
+    types::dog::{Dog, DogSpec},
+    KubeApi,
+};
+
+/// Shared context of the dogs controller.
+pub type ContextData = reconciler::ContextData<Dog, dyn DogsApi>;
+
+/// Initializes and starts the controller to watch Dog resources,
+/// until the shutdown token is cancelled.
+/// The remote client and the settings can be reloaded while the controller runs.
+pub async fn handle(
+    kube_client: Arc<dyn KubeApi<Dog> + Send + Sync>,
+    dogs_client: Arc<Reloadable<dyn DogsApi>>,
+    settings: Arc<Reloadable<ControllerSettings>>,
+    shutdown: CancellationToken,
+) -> Result<(), OperatorError> {
+    reconciler::run(kube_client, dogs_client, settings, shutdown).await
+}
+
+#[async_trait]
+impl RemoteResource<Dog> for dyn DogsApi {
+    type Dto = DogDto;
+
+    fn kube_type_to_dto(dog: &Dog) -> DogDto {
+        converters::kube_type_to_dto(dog.clone())
+    }
+
+    fn dto_to_kube_type(dto: DogDto) -> DogSpec {
+        converters::dto_to_kube_type(dto)
+    }
+
+    fn dto_uuid(dto: &DogDto) -> Option<String> {
+        converters::uuid_to_string(dto.uuid)
+    }
+
+    fn new_resource(name: &str, spec: DogSpec) -> Dog {
+        Dog::new(name, spec)
+    }
+
+    async fn create(&self, dto: DogDto) -> Result<DogDto, OperatorError> {
+        METRICS
+            .observe_remote_call("create_dog", self.create_dog(dto))
+            .await
+            .map_err(|e| OperatorError::FailedToCreateResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn get_by_id(&self, uuid: &str) -> Result<DogDto, OperatorError> {
+        METRICS
+            .observe_remote_call("get_dog_by_id", self.get_dog_by_id(uuid))
+            .await
+            .map_err(|e| OperatorError::FailedToGetResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn update_by_id(&self, uuid: &str, dto: DogDto) -> Result<DogDto, OperatorError> {
+        METRICS
+            .observe_remote_call("update_dog_by_id", self.update_dog_by_id(uuid, dto))
+            .await
+            .map_err(|e| OperatorError::FailedToUpdateResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn delete_by_id(&self, uuid: &str) -> Result<(), OperatorError> {
+        METRICS
+            .observe_remote_call("delete_dog_by_id", self.delete_dog_by_id(uuid))
+            .await
+            .map_err(|e| OperatorError::FailedToDeleteResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn list(&self) -> Result<Vec<DogDto>, OperatorError> {
+        METRICS
+            .observe_remote_call("get_dogs", self.get_dogs())
+            .await
+            .map_err(|e| OperatorError::FailedToListResources(RemoteApiError::from(e).into()))
+    }
+}
+
+/// Provides utility functions to convert between Kubernetes types
+/// and Data Transfer Objects.
+/// This is essential for synchronizing state between Kubernetes and external systems.
+pub mod converters {
+    use super::{Dog, DogDto, DogSpec};
+
+    pub use crate::controllers::reconciler::converters::{string_to_uuid, uuid_to_string};
+
+    pub fn kube_type_to_dto(dog: Dog) -> DogDto {
+        let uuid = match dog.status {
+            Some(status) => string_to_uuid(status.uuid),
+            None => None,
+        };
+        DogDto {
+            uuid,
+            name: dog.spec.name,
+            breed: dog.spec.breed,
+            age: dog.spec.age,
+        }
+    }
+
+    pub fn dto_to_kube_type(dog: DogDto) -> DogSpec {
+        DogSpec {
+            name: dog.name,
+            breed: dog.breed,
+            age: dog.age,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// This file is generated by kopgen. Do not edit manually. If you need to make adjustments add it to .openapi-generator-ignore file.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use openapi::{apis::horses_api::HorsesApi, models::Horse as HorseDto};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::ControllerSettings,
+    controllers::reconciler::{self, RemoteResource},
+    errors::{OperatorError, RemoteApiError},
+    metrics::METRICS,
+    reload::Reloadable,
+    types::horse::{Horse, HorseSpec},
+    KubeApi,
+};
+
+/// Shared context of the horses controller.
+pub type ContextData = reconciler::ContextData<Horse, dyn HorsesApi>;
+
+/// Initializes and starts the controller to watch Horse resources,
+/// until the shutdown token is cancelled.
+/// The remote client and the settings can be reloaded while the controller runs.
+pub async fn handle(
+    kube_client: Arc<dyn KubeApi<Horse> + Send + Sync>,
+    horses_client: Arc<Reloadable<dyn HorsesApi>>,
+    settings: Arc<Reloadable<ControllerSettings>>,
+    shutdown: CancellationToken,
+) -> Result<(), OperatorError> {
+    reconciler::run(kube_client, horses_client, settings, shutdown).await
+}
+
+#[async_trait]
+impl RemoteResource<Horse> for dyn HorsesApi {
+    type Dto = HorseDto;
+
+    fn kube_type_to_dto(horse: &Horse) -> HorseDto {
+        converters::kube_type_to_dto(horse.clone())
+    }
+
+    fn dto_to_kube_type(dto: HorseDto) -> HorseSpec {
+        converters::dto_to_kube_type(dto)
+    }
+
+    fn dto_uuid(dto: &HorseDto) -> Option<String> {
+        converters::uuid_to_string(dto.uuid)
+    }
+
+    fn new_resource(name: &str, spec: HorseSpec) -> Horse {
+        Horse::new(name, spec)
+    }
+
+    async fn create(&self, dto: HorseDto) -> Result<HorseDto, OperatorError> {
+        METRICS
+            .observe_remote_call("create_horse", self.create_horse(dto))
+            .await
+            .map_err(|e| OperatorError::FailedToCreateResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn get_by_id(&self, uuid: &str) -> Result<HorseDto, OperatorError> {
+        METRICS
+            .observe_remote_call("get_horse_by_id", self.get_horse_by_id(uuid))
+            .await
+            .map_err(|e| OperatorError::FailedToGetResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn update_by_id(&self, uuid: &str, dto: HorseDto) -> Result<HorseDto, OperatorError> {
+        METRICS
+            .observe_remote_call("update_horse_by_id", self.update_horse_by_id(uuid, dto))
+            .await
+            .map_err(|e| OperatorError::FailedToUpdateResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn delete_by_id(&self, uuid: &str) -> Result<(), OperatorError> {
+        METRICS
+            .observe_remote_call("delete_horse_by_id", self.delete_horse_by_id(uuid))
+            .await
+            .map_err(|e| OperatorError::FailedToDeleteResource(RemoteApiError::from(e).into()))
+    }
+
+    async fn list(&self) -> Result<Vec<HorseDto>, OperatorError> {
+        METRICS
+            .observe_remote_call("get_horses", self.get_horses())
+            .await
+            .map_err(|e| OperatorError::FailedToListResources(RemoteApiError::from(e).into()))
+    }
+}
+
+/// Provides utility functions to convert between Kubernetes types
+/// and Data Transfer Objects.
+/// This is essential for synchronizing state between Kubernetes and external systems.
+pub mod converters {
+    use super::{Horse, HorseDto, HorseSpec};
+
+    pub use crate::controllers::reconciler::converters::{string_to_uuid, uuid_to_string};
+
+    pub fn kube_type_to_dto(horse: Horse) -> HorseDto {
+        let uuid = match horse.status {
+            Some(status) => string_to_uuid(status.uuid),
+            None => None,
+        };
+        HorseDto {
+            uuid,
+            name: horse.spec.name,
+            breed: horse.spec.breed,
+            age: horse.spec.age,
+        }
+    }
+
+    pub fn dto_to_kube_type(horse: HorseDto) -> HorseSpec {
+        HorseSpec {
+            name: horse.name,
+            breed: horse.breed,
+            age: horse.age,
+        }
+    }
+}
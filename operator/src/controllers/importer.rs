@@ -0,0 +1,159 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use kube::runtime::events::{Event, EventType};
+use log::{debug, error, info, warn};
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    controllers::{
+        inventory,
+        reconciler::{RemoteResource, SyncedResource, REMOTE_ID_ANNOTATION},
+    },
+    errors::{ErrorClass, OperatorError},
+    KubeApi,
+};
+
+/// The label marking resources that were created from a remote resource by the importer.
+pub const IMPORTED_LABEL: &str = "example.com/imported";
+
+/// The outcome of an import.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// The IDs of the remote resources that no custom resource references.
+    pub unreferenced: Vec<String>,
+    /// The IDs of the remote resources that were imported.
+    pub imported: Vec<String>,
+}
+
+/// Periodically imports the remote resources that no custom resource references,
+/// until the shutdown token is cancelled.
+///
+/// # Arguments
+///
+/// * `kube_clients` - The clients of every watched namespace, used to find the referenced remote IDs.
+///   The first one creates the imported resources.
+/// * `remote_client` - The client of the remote API.
+/// * `namespace` - The namespace the imported resources are created in.
+/// * `interval` - The time between two imports.
+/// * `shutdown` - Stops importing once cancelled.
+pub async fn run<T, R>(
+    kube_clients: Vec<Arc<dyn KubeApi<T> + Send + Sync>>,
+    remote_client: Arc<R>,
+    namespace: String,
+    interval: Duration,
+    shutdown: CancellationToken,
+) where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    info!(
+        "Importing remote {} resources into namespace `{}` every {:?}",
+        kind, namespace, interval
+    );
+    let mut previous_unreferenced = HashSet::new();
+    loop {
+        match import(
+            &kube_clients,
+            remote_client.as_ref(),
+            &namespace,
+            &previous_unreferenced,
+        )
+        .await
+        {
+            Ok(report) => {
+                if report.imported.is_empty() {
+                    debug!("No remote {} resources to import", kind);
+                } else {
+                    info!(
+                        "Imported {} remote {} resources",
+                        report.imported.len(),
+                        kind
+                    );
+                }
+                previous_unreferenced = report.unreferenced.into_iter().collect();
+            }
+            Err(e) => error!("Failed to import remote {} resources: {:?}", kind, e),
+        }
+
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = shutdown.cancelled() => return,
+        }
+    }
+}
+
+/// Creates a custom resource for every remote resource that no custom resource references,
+/// either through its status or the remote ID annotation.
+/// The created resources carry the remote ID annotation, so the controller adopts
+/// the remote resource instead of creating a new one.
+/// A remote resource is only imported once it is among the `confirmed` unreferenced ones, i.e. it
+/// was found by the previous import already, so a remote resource a controller has just created
+/// is not imported before its ID is recorded in the status.
+pub async fn import<T, R>(
+    kube_clients: &[Arc<dyn KubeApi<T> + Send + Sync>],
+    remote_client: &R,
+    namespace: &str,
+    confirmed: &HashSet<String>,
+) -> Result<ImportReport, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let mut report = ImportReport::default();
+    let Some(creator) = kube_clients.first() else {
+        return Ok(report);
+    };
+    let kind = T::kind(&());
+
+    for remote_dto in inventory::unreferenced(kube_clients, remote_client).await? {
+        let Some(uuid) = R::dto_uuid(&remote_dto) else {
+            warn!("Remote {} has no uuid, cannot import it", kind);
+            continue;
+        };
+        report.unreferenced.push(uuid.clone());
+        if !confirmed.contains(&uuid) {
+            continue;
+        }
+
+        let name = imported_name(&kind, &uuid);
+        let mut resource = R::new_resource(&name, R::dto_to_kube_type(remote_dto));
+        let meta = resource.meta_mut();
+        meta.namespace = Some(namespace.to_string());
+        meta.labels
+            .get_or_insert_with(Default::default)
+            .insert(IMPORTED_LABEL.to_string(), "true".to_string());
+        meta.annotations
+            .get_or_insert_with(Default::default)
+            .insert(REMOTE_ID_ANNOTATION.to_string(), uuid.clone());
+
+        match creator.create(&resource).await {
+            Ok(created) => {
+                info!("Imported the remote {} `{}` as `{}`", kind, uuid, name);
+                let event = Event {
+                    type_: EventType::Normal,
+                    reason: "Imported".to_string(),
+                    note: Some(format!("Imported from the remote {} `{}`", kind, uuid)),
+                    action: "Import".to_string(),
+                    secondary: None,
+                };
+                if let Err(e) = creator.publish_event(&created, event).await {
+                    warn!("Failed to publish event `Imported`: {:?}", e);
+                }
+                report.imported.push(uuid);
+            }
+            Err(e) if e.class() == ErrorClass::Conflict => {
+                debug!("{} `{}` has already been imported", kind, name);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(report)
+}
+
+/// Returns the name of the resource imported from the remote resource with the given ID,
+/// e.g. `cat-0b6cfd2a-...`, which is stable so a remote resource is imported once.
+pub fn imported_name(kind: &str, uuid: &str) -> String {
+    format!("{}-{}", kind.to_lowercase(), uuid.to_lowercase())
+}
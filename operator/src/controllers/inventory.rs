@@ -0,0 +1,61 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    controllers::reconciler::{RemoteResource, SyncedResource, REMOTE_ID_ANNOTATION},
+    errors::OperatorError,
+    types::status::ResourceStatus,
+    KubeApi,
+};
+
+/// Lists the remote resources that no custom resource references,
+/// either through its status or the remote ID annotation.
+/// Remote resources the operator has released, because of a deletion policy or as tagged orphans,
+/// are left out, since no custom resource is meant to reference them anymore.
+///
+/// # Arguments
+///
+/// * `kube_clients` - The clients of every watched namespace. They share the record of the
+///   released remote IDs, which is read from the first one.
+/// * `remote_client` - The client of the remote API.
+pub async fn unreferenced<T, R>(
+    kube_clients: &[Arc<dyn KubeApi<T> + Send + Sync>],
+    remote_client: &R,
+) -> Result<Vec<R::Dto>, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let remote_dtos = remote_client.list().await?;
+    let mut referenced = HashSet::new();
+    if let Some(kube_client) = kube_clients.first() {
+        referenced.extend(kube_client.released_ids().await?);
+    }
+    for kube_client in kube_clients {
+        for resource in kube_client.list().await? {
+            referenced.extend(referenced_ids(&resource));
+        }
+    }
+
+    Ok(remote_dtos
+        .into_iter()
+        .filter(|remote_dto| R::dto_uuid(remote_dto).is_none_or(|uuid| !referenced.contains(&uuid)))
+        .collect())
+}
+
+/// Returns the remote IDs the resource references.
+fn referenced_ids<T>(resource: &T) -> impl Iterator<Item = String>
+where
+    T: SyncedResource,
+{
+    let uuid = resource
+        .status()
+        .and_then(|status| status.uuid())
+        .map(str::to_string);
+    let remote_id = resource
+        .meta()
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(REMOTE_ID_ANNOTATION))
+        .map(|remote_id| remote_id.trim().to_string());
+    uuid.into_iter().chain(remote_id)
+}
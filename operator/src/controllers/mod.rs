@@ -1,2 +1,9 @@
-
+pub mod backoff;
 pub mod cats;
+pub mod conditions;
+pub mod dogs;
+pub mod horses;
+pub mod importer;
+pub mod inventory;
+pub mod orphans;
+pub mod reconciler;
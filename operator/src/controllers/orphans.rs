@@ -0,0 +1,220 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::runtime::events::{Event, EventType, Recorder, Reporter};
+use log::{error, info, warn};
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::OrphanPolicy,
+    controllers::{
+        inventory,
+        reconciler::{RemoteResource, SyncedResource},
+    },
+    errors::OperatorError,
+    metrics::METRICS,
+    KubeApi, FIELD_MANAGER,
+};
+
+/// How many orphan IDs are listed in an event at most.
+const MAX_IDS_IN_EVENT: usize = 10;
+
+/// The outcome of a sweep.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    /// The IDs of the remote resources that no custom resource references.
+    pub orphans: Vec<String>,
+    /// The IDs of the orphans that were tagged.
+    pub tagged: Vec<String>,
+    /// The IDs of the orphans that were deleted.
+    pub deleted: Vec<String>,
+    /// The IDs of the orphans that could not be tagged or deleted.
+    pub failed: Vec<String>,
+}
+
+/// Periodically sweeps the remote resources that no custom resource references,
+/// until the shutdown token is cancelled.
+///
+/// # Arguments
+///
+/// * `kube_clients` - The clients of every watched namespace, used to find the referenced remote IDs.
+/// * `remote_client` - The client of the remote API.
+/// * `policy` - What happens to the orphans.
+/// * `interval` - The time between two sweeps.
+/// * `shutdown` - Stops sweeping once cancelled.
+pub async fn run<T, R>(
+    kube_clients: Vec<Arc<dyn KubeApi<T> + Send + Sync>>,
+    remote_client: Arc<R>,
+    policy: OrphanPolicy,
+    interval: Duration,
+    shutdown: CancellationToken,
+) where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    info!(
+        "Sweeping orphaned remote {} resources every {:?} with the {} policy",
+        kind,
+        interval,
+        policy.as_str()
+    );
+    let mut previous_orphans = HashSet::new();
+    loop {
+        // The first sweep waits as well, so the controllers can record the IDs of the
+        // remote resources they have just created.
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = shutdown.cancelled() => return,
+        }
+
+        let report = match sweep(
+            &kube_clients,
+            remote_client.as_ref(),
+            policy,
+            &previous_orphans,
+        )
+        .await
+        {
+            Ok(report) => report,
+            Err(e) => {
+                error!(
+                    "Failed to sweep orphaned remote {} resources: {:?}",
+                    kind, e
+                );
+                continue;
+            }
+        };
+
+        METRICS.orphans_found(&kind, report.orphans.len());
+        if !report.orphans.is_empty() {
+            warn!(
+                "Found {} orphaned remote {} resources: {}; tagged {}, deleted {}, failed {}",
+                report.orphans.len(),
+                kind,
+                report.orphans.join(", "),
+                report.tagged.len(),
+                report.deleted.len(),
+                report.failed.len()
+            );
+            if let Some(kube_client) = kube_clients.first() {
+                publish_sweep_event(kube_client.get_client().into_client(), &kind, &report).await;
+            }
+        }
+        previous_orphans = report.orphans.into_iter().collect();
+    }
+}
+
+/// Finds the remote resources that no custom resource references and applies the policy.
+/// An orphan is only tagged or deleted once it is among the `confirmed` orphans, i.e. it was
+/// found by the previous sweep already, so a remote resource whose ID is about to be recorded
+/// is left alone. Remote resources released by a deletion policy or tagged by an earlier sweep
+/// are not orphans anymore.
+pub async fn sweep<T, R>(
+    kube_clients: &[Arc<dyn KubeApi<T> + Send + Sync>],
+    remote_client: &R,
+    policy: OrphanPolicy,
+    confirmed: &HashSet<String>,
+) -> Result<SweepReport, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    let mut report = SweepReport::default();
+
+    for remote_dto in inventory::unreferenced(kube_clients, remote_client).await? {
+        let Some(uuid) = R::dto_uuid(&remote_dto) else {
+            continue;
+        };
+        report.orphans.push(uuid.clone());
+        if !confirmed.contains(&uuid) {
+            continue;
+        }
+
+        match policy {
+            OrphanPolicy::Report => {}
+            OrphanPolicy::Tag => {
+                let Some(kube_client) = kube_clients.first() else {
+                    continue;
+                };
+                match kube_client.record_released(&uuid, "OrphanSweep").await {
+                    Ok(()) => {
+                        METRICS.orphan_handled(&kind, "tag");
+                        report.tagged.push(uuid);
+                    }
+                    Err(e) => {
+                        error!("Failed to tag the orphaned {} `{}`: {:?}", kind, uuid, e);
+                        report.failed.push(uuid);
+                    }
+                }
+            }
+            OrphanPolicy::Delete => match remote_client.delete_by_id(&uuid).await {
+                Ok(()) => {
+                    METRICS.orphan_handled(&kind, "delete");
+                    report.deleted.push(uuid);
+                }
+                Err(e) => {
+                    error!("Failed to delete the orphaned {} `{}`: {:?}", kind, uuid, e);
+                    report.failed.push(uuid);
+                }
+            },
+        }
+    }
+    Ok(report)
+}
+
+/// Publishes the outcome of a sweep as an event regarding the operator pod,
+/// since orphans have no custom resource the event could refer to.
+async fn publish_sweep_event(client: kube::Client, kind: &str, report: &SweepReport) {
+    let (Ok(pod_name), Ok(pod_namespace)) =
+        (std::env::var("POD_NAME"), std::env::var("POD_NAMESPACE"))
+    else {
+        return;
+    };
+
+    let mut ids = report
+        .orphans
+        .iter()
+        .take(MAX_IDS_IN_EVENT)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if report.orphans.len() > MAX_IDS_IN_EVENT {
+        ids.push_str(", ...");
+    }
+    let event = Event {
+        type_: EventType::Warning,
+        reason: "OrphansFound".to_string(),
+        note: Some(format!(
+            "Found {} orphaned remote {} resources ({}); tagged {}, deleted {}, failed {}",
+            report.orphans.len(),
+            kind,
+            ids,
+            report.tagged.len(),
+            report.deleted.len(),
+            report.failed.len()
+        )),
+        action: "SweepOrphans".to_string(),
+        secondary: None,
+    };
+    let reference = ObjectReference {
+        api_version: Some("v1".to_string()),
+        kind: Some("Pod".to_string()),
+        name: Some(pod_name.clone()),
+        namespace: Some(pod_namespace),
+        ..Default::default()
+    };
+    let reporter = Reporter {
+        controller: FIELD_MANAGER.to_string(),
+        instance: Some(pod_name),
+    };
+
+    if let Err(e) = Recorder::new(client, reporter, reference)
+        .publish(event)
+        .await
+    {
+        warn!("Failed to publish event `OrphansFound`: {:?}", e);
+    }
+}
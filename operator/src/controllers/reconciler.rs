@@ -0,0 +1,1652 @@
+use std::{fmt::Debug, str::FromStr, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::{StreamExt, TryStreamExt};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use kube::{
+    api::{Patch, PatchParams, Resource},
+    core::object::{HasSpec, HasStatus},
+    runtime::{
+        controller::{self, Action},
+        events::{Event, EventType},
+        predicates, reflector, watcher, Controller, Predicate, WatchStreamExt,
+    },
+};
+use log::{debug, error, info, warn};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::ControllerSettings,
+    controllers::{
+        backoff::Backoff,
+        conditions::{
+            self, DRIFTED_CONDITION, ERROR_CONDITION, LOST_CONDITION, STALLED_CONDITION,
+            SUSPENDED_CONDITION, SYNCED_CONDITION,
+        },
+    },
+    errors::{ErrorClass, OperatorError},
+    health::{ControllerState, HEALTH},
+    metrics::METRICS,
+    reload::Reloadable,
+    types::status::{DriftReport, ResourceStatus},
+    KubeApi, FIELD_MANAGER,
+};
+
+/// How long to wait before retrying a resource that was modified concurrently.
+const CONFLICT_REQUEUE_AFTER_IN_SEC: u64 = 1;
+
+/// The annotation suspending the reconciliation of the resource when set to `true`,
+/// e.g. during an incident. Deletion is still handled according to the deletion policy.
+pub const SUSPEND_ANNOTATION: &str = "example.com/suspend";
+
+/// The annotation choosing how drift of the remote resource is handled, see [`DriftPolicy`].
+pub const DRIFT_POLICY_ANNOTATION: &str = "example.com/drift-policy";
+
+/// The annotation carrying the ID of an existing remote resource,
+/// which is adopted instead of creating a new one.
+pub const REMOTE_ID_ANNOTATION: &str = "example.com/remote-id";
+
+/// The annotation choosing what happens to the remote resource
+/// when the resource is deleted, see [`DeletionPolicy`].
+pub const DELETION_POLICY_ANNOTATION: &str = "example.com/deletion-policy";
+
+/// What happens to the remote resource when the resource is deleted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeletionPolicy {
+    /// Deletes the remote resource.
+    #[default]
+    Delete,
+    /// Leaves the remote resource untouched, e.g. so it can be adopted by another cluster.
+    Retain,
+    /// Leaves the remote resource untouched as well, but reports it as orphaned.
+    Orphan,
+}
+
+impl DeletionPolicy {
+    /// Reads the deletion policy from the annotation of the resource, defaults to [`DeletionPolicy::Delete`].
+    pub fn of<T: Resource>(resource: &T) -> Result<Self, OperatorError> {
+        resource
+            .meta()
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(DELETION_POLICY_ANNOTATION))
+            .map_or(Ok(DeletionPolicy::default()), |policy| policy.parse())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeletionPolicy::Delete => "Delete",
+            DeletionPolicy::Retain => "Retain",
+            DeletionPolicy::Orphan => "Orphan",
+        }
+    }
+}
+
+impl FromStr for DeletionPolicy {
+    type Err = OperatorError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.trim() {
+            "Delete" => Ok(DeletionPolicy::Delete),
+            "Retain" => Ok(DeletionPolicy::Retain),
+            "Orphan" => Ok(DeletionPolicy::Orphan),
+            other => Err(OperatorError::InvalidResource(format!(
+                "{} `{}` must be one of Delete, Retain, Orphan",
+                DELETION_POLICY_ANNOTATION, other
+            ))),
+        }
+    }
+}
+
+/// How a remote resource that no longer matches the spec is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DriftPolicy {
+    /// Updates the remote resource to match the spec, recreating it if it no longer exists.
+    #[default]
+    Enforce,
+    /// Leaves the remote resource as it is and only reports the drift
+    /// in the `Drifted` condition and an event.
+    /// A remote resource that no longer exists is marked as `Lost` instead of being recreated.
+    ReportOnly,
+    /// Copies the remote values into the spec.
+    AdoptRemote,
+}
+
+impl DriftPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftPolicy::Enforce => "enforce",
+            DriftPolicy::ReportOnly => "report-only",
+            DriftPolicy::AdoptRemote => "adopt-remote",
+        }
+    }
+}
+
+impl DriftPolicy {
+    /// Reads the drift policy from the annotation of the resource, defaults to [`DriftPolicy::Enforce`].
+    pub fn of<T: Resource>(resource: &T) -> Result<Self, OperatorError> {
+        resource
+            .meta()
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(DRIFT_POLICY_ANNOTATION))
+            .map_or(Ok(DriftPolicy::default()), |policy| policy.parse())
+    }
+}
+
+impl FromStr for DriftPolicy {
+    type Err = OperatorError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.trim() {
+            "enforce" => Ok(DriftPolicy::Enforce),
+            "report-only" => Ok(DriftPolicy::ReportOnly),
+            "adopt-remote" => Ok(DriftPolicy::AdoptRemote),
+            other => Err(OperatorError::InvalidResource(format!(
+                "{} `{}` must be one of enforce, report-only, adopt-remote",
+                DRIFT_POLICY_ANNOTATION, other
+            ))),
+        }
+    }
+}
+
+/// A custom resource whose spec is mirrored to a remote API.
+///
+/// Implemented automatically for every CRD type whose spec can be compared
+/// and whose status implements [`ResourceStatus`].
+pub trait SyncedResource:
+    Resource<DynamicType = ()>
+    + HasSpec<Spec = Self::SyncedSpec>
+    + HasStatus<Status = Self::SyncedStatus>
+    + Clone
+    + Debug
+    + DeserializeOwned
+    + Serialize
+    + Send
+    + Sync
+    + 'static
+{
+    type SyncedSpec: PartialEq + Clone + Debug + Serialize + Send + Sync;
+    type SyncedStatus: ResourceStatus + Serialize;
+}
+
+impl<T> SyncedResource for T
+where
+    T: Resource<DynamicType = ()>
+        + HasSpec
+        + HasStatus
+        + Clone
+        + Debug
+        + DeserializeOwned
+        + Serialize
+        + Send
+        + Sync
+        + 'static,
+    T::Spec: PartialEq + Clone + Debug + Serialize + Send + Sync,
+    T::Status: ResourceStatus + Serialize,
+{
+    type SyncedSpec = T::Spec;
+    type SyncedStatus = T::Status;
+}
+
+/// Describes how a custom resource maps onto its remote counterpart:
+/// how to convert between the spec and the DTO, and which remote calls
+/// perform the CRUD operations.
+#[async_trait]
+pub trait RemoteResource<T: SyncedResource>: Send + Sync {
+    type Dto: Clone + Debug + Send + Sync;
+
+    /// Converts the Kubernetes resource into the DTO sent to the remote API.
+    fn kube_type_to_dto(resource: &T) -> Self::Dto;
+
+    /// Converts a DTO received from the remote API into a Kubernetes spec.
+    fn dto_to_kube_type(dto: Self::Dto) -> T::SyncedSpec;
+
+    /// Returns the remote ID carried by the DTO.
+    fn dto_uuid(dto: &Self::Dto) -> Option<String>;
+
+    /// Builds a new Kubernetes resource with the given name and spec.
+    fn new_resource(name: &str, spec: T::SyncedSpec) -> T;
+
+    async fn create(&self, dto: Self::Dto) -> Result<Self::Dto, OperatorError>;
+
+    async fn get_by_id(&self, uuid: &str) -> Result<Self::Dto, OperatorError>;
+
+    async fn update_by_id(&self, uuid: &str, dto: Self::Dto) -> Result<Self::Dto, OperatorError>;
+
+    async fn delete_by_id(&self, uuid: &str) -> Result<(), OperatorError>;
+
+    async fn list(&self) -> Result<Vec<Self::Dto>, OperatorError>;
+}
+
+/// - Holds shared clients for interacting with Kubernetes (kube_client).
+/// - Holds shared clients for interacting with the external system.
+/// - Holds the settings the controller runs with, which can be reloaded while it runs.
+/// - Holds the retry backoff of the resources that failed to reconcile.
+pub struct ContextData<T, R>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    pub kube_client: Arc<dyn KubeApi<T> + Send + Sync>,
+    pub remote_client: Arc<R>,
+    pub settings: Arc<Reloadable<ControllerSettings>>,
+    pub backoff: Backoff,
+}
+
+impl<T, R> ContextData<T, R>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    pub fn new(kube_client: Arc<dyn KubeApi<T> + Send + Sync>, remote_client: Arc<R>) -> Self {
+        Self::with_settings(
+            kube_client,
+            remote_client,
+            Arc::new(Reloadable::new(Arc::new(ControllerSettings::default()))),
+        )
+    }
+
+    pub fn with_settings(
+        kube_client: Arc<dyn KubeApi<T> + Send + Sync>,
+        remote_client: Arc<R>,
+        settings: Arc<Reloadable<ControllerSettings>>,
+    ) -> Self {
+        let initial_settings = settings.get();
+        Self {
+            kube_client,
+            remote_client,
+            backoff: Backoff::new(initial_settings.backoff_base, initial_settings.backoff_max),
+            settings,
+        }
+    }
+}
+
+/// Initializes and starts the controller to watch resources.
+/// Flow:
+///   - Creates a ContextData instance.
+///   - Sets up the controller with the Kubernetes client, only triggered by changes of the
+///     generation, annotations or finalizers, so writing the status doesn't trigger it again.
+///   - Counts the triggered resources that wait for their reconciliation in the queue depth metric.
+///   - Defines the reconciliation logic (reconcile) and error handling (error_policy).
+///   - Starts the event loop to process reconciliation results.
+///   - Stops accepting new work once shutdown is requested and waits for running reconciliations.
+pub async fn run<T, R>(
+    kube_client: Arc<dyn KubeApi<T> + Send + Sync>,
+    remote_client: Arc<R>,
+    settings: Arc<Reloadable<ControllerSettings>>,
+    shutdown: CancellationToken,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized + 'static,
+{
+    let kind = T::kind(&());
+    info!("Starting the {} controller", kind);
+    let concurrency = settings.get().concurrency;
+    let context = Arc::new(ContextData::with_settings(
+        kube_client.clone(),
+        remote_client,
+        settings,
+    ));
+    let underlying_kube_api_client = kube_client.get_client();
+    let controller_name = underlying_kube_api_client.resource_url().to_string();
+    HEALTH.set_controller_state(&controller_name, ControllerState::Starting);
+
+    let (reader, writer) = reflector::store();
+    let resources = watcher(
+        underlying_kube_api_client.clone(),
+        watcher::Config::default(),
+    )
+    .default_backoff()
+    .reflect(writer)
+    .applied_objects()
+    .predicate_filter(
+        predicates::generation
+            .combine(predicates::annotations)
+            .combine(predicates::finalizers),
+    )
+    .inspect_ok(|resource| METRICS.reconcile_queued(&T::kind(&()), &backoff_key(resource)));
+    let controller = Controller::for_stream(resources, reader)
+        .owns(underlying_kube_api_client, watcher::Config::default())
+        .with_config(controller::Config::default().concurrency(concurrency))
+        .graceful_shutdown_on(shutdown.clone().cancelled_owned());
+
+    // The watch is live once the initial list of resources has been received.
+    let store = controller.store();
+    let watched_controller_name = controller_name.clone();
+    let watch_ready = tokio::spawn(async move {
+        if store.wait_until_ready().await.is_ok() {
+            HEALTH.set_controller_state(&watched_controller_name, ControllerState::Watching);
+        }
+    });
+
+    controller
+        .run(reconcile, error_policy, context)
+        .for_each(|res| async {
+            match res {
+                Ok(action) => {
+                    HEALTH.set_controller_state(&controller_name, ControllerState::Watching);
+                    info!("Reconciliation was successful, action: {:?}", action)
+                }
+                Err(controller::Error::QueueError(e)) => {
+                    HEALTH.set_controller_state(&controller_name, ControllerState::WatchFailing);
+                    error!("Error watching {}: {:?}", kind, e)
+                }
+                Err(e) => {
+                    if let controller::Error::ObjectNotFound(object_ref) = &e {
+                        // Deleted before its reconciliation started, so it is not waiting anymore.
+                        METRICS.reconcile_dequeued(
+                            &kind,
+                            &format!(
+                                "{}/{}",
+                                object_ref.namespace.as_deref().unwrap_or_default(),
+                                object_ref.name
+                            ),
+                        );
+                    }
+                    HEALTH.set_controller_state(&controller_name, ControllerState::Watching);
+                    error!("Error reconciling: {:?}", e)
+                }
+            }
+        })
+        .await;
+
+    watch_ready.abort();
+    METRICS.clear_queue(&kind);
+    if shutdown.is_cancelled() {
+        info!("{} Controller has stopped", kind);
+    } else {
+        HEALTH.set_controller_state(&controller_name, ControllerState::Stopped);
+        error!("{} Controller has stopped unexpectedly", kind);
+    }
+    Ok(())
+}
+
+/// Ensures the actual state of a resource matches the desired state.
+/// Flow:
+///   - Initialization: Clones necessary clients and extracts the uuid from the resource status.
+///   - Status Setup: Adds default status if it's missing.
+///   - Suspension: Skips everything but deletion while the suspend annotation is set.
+///   - Operation Handling:
+///   - Deletion: If the resource is marked for deletion, invokes handle_delete.
+///   - Creation: If there's no uuid, it's a new resource; invokes handle_create,
+///     which adopts the remote resource referenced by the remote ID annotation, if any.
+///   - Update: If the generation has changed, invokes handle_update.
+///   - Drift Detection: Calls check_for_drift to synchronize any discrepancies between local and remote states.
+///   - Requeue: Schedules the next reconciliation after a defined interval.
+///   - Metrics: Records the outcome and duration of the reconciliation.
+pub async fn reconcile<T, R>(
+    resource: Arc<T>,
+    ctx: Arc<ContextData<T, R>>,
+) -> Result<Action, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let key = backoff_key(resource.as_ref());
+    METRICS.reconcile_dequeued(&T::kind(&()), &key);
+    let timer = METRICS.reconcile_started(&T::kind(&()));
+    let result = reconcile_resource(resource, ctx.clone()).await;
+    if result.is_ok() {
+        ctx.backoff.reset(&key);
+    }
+    timer.finish(&result);
+    result
+}
+
+/// Performs the reconciliation described in [`reconcile`], recording a failure in the status
+/// before it is handed to [`error_policy`].
+async fn reconcile_resource<T, R>(
+    resource: Arc<T>,
+    ctx: Arc<ContextData<T, R>>,
+) -> Result<Action, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kube_client = ctx.kube_client.clone();
+    let remote_client = ctx.remote_client.clone();
+    let mut resource = resource.as_ref().clone();
+
+    if resource.status().is_none() {
+        add_default_status(&mut resource).await?;
+    }
+
+    let result = reconcile_steps(
+        kube_client.as_ref(),
+        remote_client.as_ref(),
+        &mut resource,
+        ctx.settings.get().requeue_after,
+    )
+    .await;
+    if let Err(e) = &result {
+        record_failure(kube_client.as_ref(), &mut resource, e).await;
+    }
+    result
+}
+
+/// Runs the steps described in [`reconcile`] and records the successful ones in the status.
+async fn reconcile_steps<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    requeue_after: Duration,
+) -> Result<Action, OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let uuid = resource
+        .status()
+        .and_then(|status| status.uuid())
+        .unwrap_or_default()
+        .to_string();
+
+    if resource.meta().deletion_timestamp.is_none() {
+        let suspended = is_suspended(kube_client, resource).await;
+        record_suspension(kube_client, resource, suspended).await?;
+        if suspended {
+            debug!(
+                "{} is suspended, waiting for it to be resumed",
+                T::kind(&())
+            );
+            return Ok(Action::await_change());
+        }
+    }
+
+    if resource.meta().deletion_timestamp.is_none() && is_stalled(resource) {
+        debug!(
+            "{} has failed permanently, waiting for a change before retrying",
+            T::kind(&())
+        );
+        return Ok(Action::await_change());
+    }
+
+    sync_resource(kube_client, remote_client, resource, &uuid).await?;
+
+    if resource.meta().deletion_timestamp.is_none() {
+        record_deletion_policy(kube_client, resource).await;
+        mark_reconciled(kube_client, resource).await?;
+    }
+    Ok(Action::requeue(requeue_after))
+}
+
+/// Deletes, creates or updates the remote resource depending on the state of the
+/// Kubernetes resource, then corrects any drift unless the resource is being deleted.
+/// An update of a remote resource that no longer exists is left to the drift check,
+/// which recreates it or marks it as lost.
+async fn sync_resource<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    uuid: &str,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let generation = resource.meta().generation;
+    let observed = resource
+        .status()
+        .is_some_and(|status| status.is_observed(generation));
+
+    if resource.meta().deletion_timestamp.is_some() {
+        return handle_delete(kube_client, remote_client, resource, uuid).await;
+    } else if uuid.is_empty() {
+        handle_create(kube_client, remote_client, resource).await?;
+    } else if !observed {
+        match handle_update(kube_client, remote_client, resource, uuid).await {
+            Err(e) if e.class() == ErrorClass::NotFound => warn!(
+                "The remote {} `{}` no longer exists, leaving it to the drift check",
+                T::kind(&()),
+                uuid
+            ),
+            result => result?,
+        }
+    }
+
+    check_for_drift(kube_client, remote_client, resource).await
+}
+
+/// Checks whether the reconciliation of the resource is suspended by the suspend annotation.
+/// An invalid value is reported and suspends the reconciliation, since it was most likely
+/// meant to.
+async fn is_suspended<T>(kube_client: &dyn KubeApi<T>, resource: &T) -> bool
+where
+    T: SyncedResource,
+{
+    let Some(suspend) = resource
+        .meta()
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(SUSPEND_ANNOTATION))
+    else {
+        return false;
+    };
+    match suspend.trim().parse::<bool>() {
+        Ok(suspended) => suspended,
+        Err(_) => {
+            let message = format!(
+                "{} `{}` must be true or false, suspending the reconciliation",
+                SUSPEND_ANNOTATION, suspend
+            );
+            warn!("{}", message);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "InvalidSuspendAnnotation",
+                "Reconcile",
+                message,
+            )
+            .await;
+            true
+        }
+    }
+}
+
+/// Records in the `Suspended` condition whether the reconciliation is suspended,
+/// publishing an event whenever it is suspended or resumed.
+async fn record_suspension<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+    suspended: bool,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    if conditions::is_true(resource, SUSPENDED_CONDITION) == suspended {
+        return Ok(());
+    }
+
+    let kind = T::kind(&());
+    let (reason, message) = if suspended {
+        info!("Suspending the reconciliation of {}", kind);
+        (
+            "Suspended",
+            format!(
+                "The reconciliation is suspended by {}, the remote {} is left as it is",
+                SUSPEND_ANNOTATION, kind
+            ),
+        )
+    } else {
+        info!("Resuming the reconciliation of {}", kind);
+        ("Resumed", "The reconciliation has been resumed".to_string())
+    };
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        reason,
+        "Reconcile",
+        message.clone(),
+    )
+    .await;
+    conditions::set(resource, SUSPENDED_CONDITION, suspended, reason, message);
+    kube_client.update_status(resource).await
+}
+
+/// Checks whether the resource has failed permanently in its current generation.
+fn is_stalled<T>(resource: &T) -> bool
+where
+    T: SyncedResource,
+{
+    let generation = resource.meta().generation;
+    conditions::find(resource, STALLED_CONDITION).is_some_and(|condition| {
+        condition.status == "True" && condition.observed_generation == generation
+    })
+}
+
+/// Surfaces a permanent failure in the conditions, so the resource is not retried
+/// until it changes.
+async fn mark_stalled<T>(kube_client: &dyn KubeApi<T>, resource: &mut T, error: &OperatorError)
+where
+    T: SyncedResource,
+{
+    let kind = T::kind(&());
+    warn!(
+        "{} has failed permanently and won't be retried until it changes: {}",
+        kind, error
+    );
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Warning,
+        "Stalled",
+        "Reconcile",
+        format!(
+            "Failed permanently, won't retry until the {} changes: {}",
+            kind, error
+        ),
+    )
+    .await;
+
+    conditions::set(
+        resource,
+        STALLED_CONDITION,
+        true,
+        error.variant_name(),
+        error.to_string(),
+    );
+    conditions::set_error(resource, error);
+}
+
+/// Records a failed reconciliation in the status: the failed attempt, and depending on
+/// the class of the error the `Stalled` or `Error` condition.
+/// Failing to record it is only logged, since the error is handled by [`error_policy`] anyway.
+async fn record_failure<T>(kube_client: &dyn KubeApi<T>, resource: &mut T, error: &OperatorError)
+where
+    T: SyncedResource,
+{
+    match error.class() {
+        ErrorClass::Permanent => mark_stalled(kube_client, resource, error).await,
+        // Retried shortly, so not worth reporting in the conditions.
+        ErrorClass::Conflict => {}
+        _ => conditions::set_error(resource, error),
+    }
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.sync_mut().failed(Time(Utc::now()), error);
+    }
+
+    if let Err(e) = kube_client.update_status(resource).await {
+        warn!("Failed to record the failure of {}: {:?}", T::kind(&()), e);
+    }
+}
+
+/// Records a successful reconciliation in the status: the spec has been synced
+/// and previous failures are cleared. A remote resource that is lost has not been synced,
+/// so only the failures are cleared. The conditions of earlier versions are dropped.
+async fn mark_reconciled<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    conditions::remove_legacy(resource);
+    for type_ in [STALLED_CONDITION, ERROR_CONDITION] {
+        if conditions::is_true(resource, type_) {
+            conditions::set(
+                resource,
+                type_,
+                false,
+                "Reconciled",
+                "The resource has been reconciled".to_string(),
+            );
+        }
+    }
+    if conditions::is_true(resource, LOST_CONDITION) {
+        return kube_client.update_status(resource).await;
+    }
+    if !conditions::is_true(resource, SYNCED_CONDITION) {
+        conditions::set(
+            resource,
+            SYNCED_CONDITION,
+            true,
+            "Reconciled",
+            format!("The spec has been applied to the remote {}", T::kind(&())),
+        );
+    }
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.sync_mut().succeeded(Time(Utc::now()));
+    }
+
+    kube_client.update_status(resource).await
+}
+
+/// Records the deletion policy in the status, so it is visible before the resource is deleted.
+/// An invalid policy is reported, the resource is only kept from being deleted once it is deleted.
+/// The status is written along with the outcome of the reconciliation.
+async fn record_deletion_policy<T>(kube_client: &dyn KubeApi<T>, resource: &mut T)
+where
+    T: SyncedResource,
+{
+    let policy = match DeletionPolicy::of(resource) {
+        Ok(policy) => policy,
+        Err(e) => {
+            warn!("{}", e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "InvalidDeletionPolicy",
+                "Reconcile",
+                format!(
+                    "{}, the remote resource won't be deleted until it is fixed",
+                    e
+                ),
+            )
+            .await;
+            return;
+        }
+    };
+
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.set_deletion_policy(policy.as_str().to_string());
+    }
+}
+
+/// Initializes the status field of a resource with default values.
+pub async fn add_default_status<T>(resource: &mut T) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    resource
+        .status_mut()
+        .get_or_insert_with(T::SyncedStatus::default);
+    Ok(())
+}
+
+/// Ensures consistency between the local Kubernetes resource
+/// and its remote counterpart by detecting and resolving any discrepancies.
+/// How a discrepancy is resolved depends on the [`DriftPolicy`] of the resource,
+/// which also decides whether a remote resource that no longer exists is recreated.
+/// The fields that differed are recorded in the status as the last drift.
+pub async fn check_for_drift<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    let dto = R::kube_type_to_dto(resource);
+    let uuid = R::dto_uuid(&dto).unwrap_or_default();
+
+    if uuid.is_empty() {
+        warn!(
+            "{} has no status, cannot get by id or check for drift. Skipping...",
+            kind
+        );
+        return Ok(());
+    }
+
+    let remote_dto = match remote_client.get_by_id(&uuid).await {
+        Ok(remote_dto) => remote_dto,
+        Err(e) if e.class() == ErrorClass::NotFound => {
+            return handle_lost(kube_client, remote_client, resource, &uuid).await;
+        }
+        Err(e) => {
+            error!("Failed to get {}: {:?}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "DriftCheckFailed",
+                "CheckForDrift",
+                format!("Failed to get the remote {} `{}`: {}", kind, uuid, e),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+
+    if conditions::is_true(resource, LOST_CONDITION) {
+        info!("The remote {} `{}` exists again", kind, uuid);
+        conditions::set(
+            resource,
+            LOST_CONDITION,
+            false,
+            "Found",
+            format!("The remote {} exists", kind),
+        );
+        kube_client.update_status(resource).await?;
+    }
+
+    let remote_spec = R::dto_to_kube_type(remote_dto);
+    if &remote_spec == resource.spec() {
+        return clear_drifted(kube_client, resource).await;
+    }
+
+    let policy = drift_policy(kube_client, resource).await;
+    let mut report = DriftReport::between(
+        resource.spec(),
+        &remote_spec,
+        policy.as_str(),
+        Time(Utc::now()),
+    );
+    match policy {
+        DriftPolicy::Enforce => {}
+        DriftPolicy::ReportOnly => return report_drift(kube_client, resource, &uuid, report).await,
+        DriftPolicy::AdoptRemote => {
+            return adopt_remote(kube_client, resource, remote_spec, &uuid, report).await
+        }
+    }
+
+    let summary = report.summary();
+    warn!(
+        "{} has drifted remotely ({}), sending an update to remote...",
+        kind, summary
+    );
+    if let Err(e) = remote_client
+        .update_by_id(&uuid, R::kube_type_to_dto(resource))
+        .await
+    {
+        error!("Failed to update {}: {:?}", kind, e);
+        publish_event(
+            kube_client,
+            resource,
+            EventType::Warning,
+            "DriftCorrectionFailed",
+            "CheckForDrift",
+            format!(
+                "Failed to correct the drift of the remote {} `{}`: {}",
+                kind, uuid, e
+            ),
+        )
+        .await;
+        record_drift(resource, report);
+        return Err(e);
+    }
+
+    info!("{} updated successfully", kind);
+    METRICS.drift_corrected(&kind);
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        "DriftCorrected",
+        "CheckForDrift",
+        format!(
+            "The remote {} `{}` has drifted and was updated to match the spec: {}",
+            kind, uuid, summary
+        ),
+    )
+    .await;
+    report.corrected = true;
+    record_drift(resource, report);
+    let generation = resource.meta().generation;
+    let mut resource_clone = resource.clone();
+    if let Some(status) = resource_clone.status_mut().as_mut() {
+        status.set_observed_generation(generation);
+    }
+    conditions::set(
+        &mut resource_clone,
+        SYNCED_CONDITION,
+        true,
+        "DriftCorrected",
+        format!("The remote {} was updated to match the spec", kind),
+    );
+    kube_client.update_status(&mut resource_clone).await
+}
+
+/// Reads the drift policy of the resource, falling back to [`DriftPolicy::ReportOnly`]
+/// with a warning event when the annotation is invalid.
+async fn drift_policy<T>(kube_client: &dyn KubeApi<T>, resource: &T) -> DriftPolicy
+where
+    T: SyncedResource,
+{
+    match DriftPolicy::of(resource) {
+        Ok(policy) => policy,
+        Err(e) => {
+            warn!("{}, only reporting the drift", e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "InvalidDriftPolicy",
+                "CheckForDrift",
+                format!("{}, only reporting the drift", e),
+            )
+            .await;
+            DriftPolicy::ReportOnly
+        }
+    }
+}
+
+/// Handles a remote resource that no longer exists, e.g. because it was deleted outside of
+/// the operator. Under the enforce drift policy it is recreated from the spec and its new ID
+/// is recorded, otherwise the resource is only marked as `Lost`.
+async fn handle_lost<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    uuid: &str,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    let policy = drift_policy(kube_client, resource).await;
+    if policy != DriftPolicy::Enforce {
+        if conditions::is_true(resource, LOST_CONDITION) {
+            debug!("The remote {} `{}` is still lost", kind, uuid);
+            return Ok(());
+        }
+
+        warn!(
+            "The remote {} `{}` no longer exists, marking it as lost",
+            kind, uuid
+        );
+        publish_event(
+            kube_client,
+            resource,
+            EventType::Warning,
+            "RemoteLost",
+            "CheckForDrift",
+            format!(
+                "The remote {} `{}` no longer exists and is not recreated because of the {} drift policy",
+                kind,
+                uuid,
+                policy.as_str()
+            ),
+        )
+        .await;
+        conditions::set(
+            resource,
+            LOST_CONDITION,
+            true,
+            "RemoteNotFound",
+            format!("The remote {} `{}` no longer exists", kind, uuid),
+        );
+        conditions::set(
+            resource,
+            SYNCED_CONDITION,
+            false,
+            "RemoteNotFound",
+            format!("The remote {} no longer exists", kind),
+        );
+        return kube_client.update_status(resource).await;
+    }
+
+    warn!(
+        "The remote {} `{}` no longer exists, recreating it...",
+        kind, uuid
+    );
+    // A resource without status, so the remote API assigns a new ID.
+    let name = resource.meta().name.clone().unwrap_or_default();
+    let dto = R::kube_type_to_dto(&R::new_resource(&name, resource.spec().clone()));
+    let remote_dto = match remote_client.create(dto).await {
+        Ok(remote_dto) => remote_dto,
+        Err(e) => {
+            error!("Failed to recreate {}: {:?}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "RecreateFailed",
+                "CheckForDrift",
+                format!(
+                    "Failed to recreate the remote {} `{}`, which no longer exists: {}",
+                    kind, uuid, e
+                ),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+    let Some(new_uuid) = R::dto_uuid(&remote_dto) else {
+        warn!("Remote {} has no uuid, cannot update status", kind);
+        return Ok(());
+    };
+
+    METRICS.drift_corrected(&kind);
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        "Recreated",
+        "CheckForDrift",
+        format!(
+            "The remote {} `{}` no longer existed and was recreated as `{}`",
+            kind, uuid, new_uuid
+        ),
+    )
+    .await;
+    let generation = resource.meta().generation;
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.set_uuid(new_uuid);
+        status.set_observed_generation(generation);
+    }
+    conditions::set(
+        resource,
+        SYNCED_CONDITION,
+        true,
+        "Recreated",
+        format!("The remote {} was recreated from the spec", kind),
+    );
+    conditions::set(
+        resource,
+        LOST_CONDITION,
+        false,
+        "Recreated",
+        format!("The remote {} was recreated", kind),
+    );
+    kube_client.update_status(resource).await?;
+    info!("Recreated the remote {}", kind);
+    Ok(())
+}
+
+/// Records the drift in the status of the resource, written along with the outcome of
+/// handling it.
+fn record_drift<T>(resource: &mut T, report: DriftReport)
+where
+    T: SyncedResource,
+{
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.set_last_drift(report);
+    }
+}
+
+/// Reports that the remote resource differs from the spec, without changing either of them.
+/// The event is only published when the resource starts drifting or drifts differently.
+async fn report_drift<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+    uuid: &str,
+    report: DriftReport,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    let kind = T::kind(&());
+    let reported = resource
+        .status()
+        .and_then(|status| status.last_drift())
+        .is_some_and(|last_drift| last_drift.same_drift(&report));
+    if conditions::is_true(resource, DRIFTED_CONDITION) && reported {
+        debug!("{} is still drifted, reporting only", kind);
+        return Ok(());
+    }
+
+    warn!(
+        "{} has drifted remotely ({}), reporting only",
+        kind,
+        report.summary()
+    );
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Warning,
+        "DriftDetected",
+        "CheckForDrift",
+        format!(
+            "The remote {} `{}` differs from the spec and is left as it is by the report-only drift policy: {}",
+            kind,
+            uuid,
+            report.summary()
+        ),
+    )
+    .await;
+    record_drift(resource, report);
+    conditions::set(
+        resource,
+        DRIFTED_CONDITION,
+        true,
+        "DriftDetected",
+        format!("The remote {} differs from the spec", kind),
+    );
+    kube_client.update_status(resource).await
+}
+
+/// Copies the values of the remote resource into the spec of the Kubernetes resource.
+async fn adopt_remote<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+    remote_spec: T::SyncedSpec,
+    uuid: &str,
+    report: DriftReport,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    let kind = T::kind(&());
+    let summary = report.summary();
+    warn!(
+        "{} has drifted remotely ({}), adopting the remote values into the spec...",
+        kind, summary
+    );
+    record_drift(resource, report);
+
+    // The resource version makes the patch fail with a conflict if the spec was changed
+    // since the drift was detected, instead of overwriting the change.
+    let patch = Patch::Merge(json!({
+        "metadata": { "resourceVersion": resource.meta().resource_version },
+        "spec": remote_spec,
+    }));
+    let mut adopted = match kube_client
+        .patch(resource, &PatchParams::apply(FIELD_MANAGER), &patch)
+        .await
+    {
+        Ok(adopted) => adopted,
+        Err(e) => {
+            error!("Failed to adopt the remote {}: {:?}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "DriftAdoptionFailed",
+                "CheckForDrift",
+                format!(
+                    "Failed to copy the remote {} `{}` into the spec: {}",
+                    kind, uuid, e
+                ),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+
+    // The spec now matches the remote resource, so the new generation has been synchronized.
+    let generation = adopted.meta().generation;
+    *adopted.status_mut() = resource.status().cloned();
+    if let Some(status) = adopted.status_mut().as_mut() {
+        status.set_observed_generation(generation);
+    }
+    conditions::set(
+        &mut adopted,
+        DRIFTED_CONDITION,
+        false,
+        "AdoptedRemote",
+        format!(
+            "The remote values of the {} were copied into the spec",
+            kind
+        ),
+    );
+    kube_client.update_status(&mut adopted).await?;
+
+    publish_event(
+        kube_client,
+        &adopted,
+        EventType::Normal,
+        "DriftAdopted",
+        "CheckForDrift",
+        format!(
+            "The remote {} `{}` has drifted and its values were copied into the spec: {}",
+            kind, uuid, summary
+        ),
+    )
+    .await;
+    *resource = adopted;
+    Ok(())
+}
+
+/// Clears the `Drifted` condition once the remote resource matches the spec again.
+async fn clear_drifted<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    if !conditions::is_true(resource, DRIFTED_CONDITION) {
+        return Ok(());
+    }
+
+    conditions::set(
+        resource,
+        DRIFTED_CONDITION,
+        false,
+        "InSync",
+        "The remote resource matches the spec".to_string(),
+    );
+    kube_client.update_status(resource).await
+}
+
+/// Defines how the controller should respond to errors during reconciliation,
+/// depending on the class of the error:
+///   - Permanent: Stops retrying until the resource changes.
+///   - Conflict: Retries shortly, with the latest version of the resource.
+///   - Retryable, NotFound, Unauthorized: Retries, backing off exponentially
+///     while the same resource keeps failing.
+pub fn error_policy<T, R>(
+    resource: Arc<T>,
+    error: &OperatorError,
+    ctx: Arc<ContextData<T, R>>,
+) -> Action
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    let key = backoff_key(resource.as_ref());
+    match error.class() {
+        ErrorClass::Permanent => {
+            error!(
+                "Permanent error processing event for {} `{}`, not retrying: {:?}",
+                kind, key, error
+            );
+            Action::await_change()
+        }
+        ErrorClass::Conflict => {
+            warn!(
+                "Conflict processing event for {} `{}`, retrying: {:?}",
+                kind, key, error
+            );
+            Action::requeue(Duration::from_secs(CONFLICT_REQUEUE_AFTER_IN_SEC))
+        }
+        class => {
+            let delay = ctx.backoff.next_delay(&key);
+            if class == ErrorClass::Unauthorized {
+                error!("The credentials were rejected, check the access token");
+            }
+            error!(
+                "Error processing event for {} `{}` (failure {}), retrying in {:?}: {:?}",
+                kind,
+                key,
+                ctx.backoff.failures(&key),
+                delay,
+                error
+            );
+            Action::requeue(delay)
+        }
+    }
+}
+
+/// Identifies a resource in the retry backoff.
+fn backoff_key<T>(resource: &T) -> String
+where
+    T: SyncedResource,
+{
+    format!(
+        "{}/{}",
+        resource.meta().namespace.as_deref().unwrap_or_default(),
+        resource.meta().name.as_deref().unwrap_or_default()
+    )
+}
+
+/// Deletes, retains or orphans the remote resource depending on the [`DeletionPolicy`],
+/// and removes the finalizer from the local Kubernetes resource.
+pub async fn handle_delete<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    uuid: &str,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    if uuid.is_empty() {
+        warn!("{} has no status, cannot delete by id. Skipping...", kind);
+        return Ok(());
+    }
+
+    let policy = match DeletionPolicy::of(resource) {
+        Ok(policy) => policy,
+        Err(e) => {
+            error!("Not deleting {}: {}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "InvalidDeletionPolicy",
+                "Delete",
+                format!(
+                    "{}, the remote {} `{}` is kept until it is fixed",
+                    e, kind, uuid
+                ),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+    if policy != DeletionPolicy::Delete {
+        return release_remote(kube_client, resource, uuid, policy).await;
+    }
+
+    match remote_client.delete_by_id(uuid).await {
+        Ok(()) => {}
+        Err(e) if e.class() == ErrorClass::NotFound => {
+            info!(
+                "The remote {} `{}` no longer exists, removing the finalizer",
+                kind, uuid
+            );
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Normal,
+                "AlreadyDeleted",
+                "Delete",
+                format!("The remote {} `{}` had already been deleted", kind, uuid),
+            )
+            .await;
+            return kube_client.remove_finalizer(resource).await;
+        }
+        Err(e) => {
+            error!("Failed to delete {}: {:?}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "DeleteFailed",
+                "Delete",
+                format!("Failed to delete the remote {} `{}`: {}", kind, uuid, e),
+            )
+            .await;
+            return Err(e);
+        }
+    }
+
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        "Deleted",
+        "Delete",
+        format!("Deleted the remote {} `{}`", kind, uuid),
+    )
+    .await;
+    kube_client.remove_finalizer(resource).await?;
+    info!("Successfully deleted {}", kind);
+    Ok(())
+}
+
+/// Leaves the remote resource behind without touching it, depending on the [`DeletionPolicy`],
+/// and removes the finalizer. The remote ID is recorded as released by the operator, which keeps
+/// the importer and the orphan sweep away from the remote resource.
+async fn release_remote<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &mut T,
+    uuid: &str,
+    policy: DeletionPolicy,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+{
+    let kind = T::kind(&());
+    let (released, failed) = match policy {
+        DeletionPolicy::Retain => ("Retained", "RetainFailed"),
+        _ => ("Orphaned", "OrphanFailed"),
+    };
+    if let Err(e) = kube_client.record_released(uuid, policy.as_str()).await {
+        error!("Failed to record the {} as released: {:?}", kind, e);
+        publish_event(
+            kube_client,
+            resource,
+            EventType::Warning,
+            failed,
+            "Delete",
+            format!(
+                "Failed to record the remote {} `{}` as released: {}",
+                kind, uuid, e
+            ),
+        )
+        .await;
+        return Err(e);
+    }
+
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        released,
+        "Delete",
+        format!(
+            "Left the remote {} `{}` behind because of the {} deletion policy",
+            kind,
+            uuid,
+            policy.as_str()
+        ),
+    )
+    .await;
+    kube_client.remove_finalizer(resource).await?;
+    info!("{} the remote {}", released, kind);
+    Ok(())
+}
+
+/// Updates the remote resource and synchronizes the local Kubernetes resource.
+pub async fn handle_update<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    uuid: &str,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    if uuid.is_empty() {
+        return Err(OperatorError::InvalidResource("uuid is empty".to_string()));
+    }
+
+    let kind = T::kind(&());
+    let dto = R::kube_type_to_dto(resource);
+    if let Err(e) = remote_client.update_by_id(uuid, dto).await {
+        error!("Failed to update {}: {:?}", kind, e);
+        publish_event(
+            kube_client,
+            resource,
+            EventType::Warning,
+            "UpdateFailed",
+            "Update",
+            format!("Failed to update the remote {} `{}`: {}", kind, uuid, e),
+        )
+        .await;
+        return Err(e);
+    }
+
+    let generation = resource.meta().generation;
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.set_observed_generation(generation);
+    }
+    conditions::set(
+        resource,
+        SYNCED_CONDITION,
+        true,
+        "Updated",
+        format!("The remote {} was updated to match the spec", kind),
+    );
+    kube_client.update_status(resource).await?;
+
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        "Updated",
+        "Update",
+        format!("Updated the remote {} `{}`", kind, uuid),
+    )
+    .await;
+    info!("Updated a {} by id went successfully", kind);
+    Ok(())
+}
+
+/// Creates a corresponding remote resource and
+/// updates the local Kubernetes resource with necessary metadata.
+pub async fn handle_create<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    if resource.status().is_none() {
+        add_default_status(resource).await?;
+    }
+
+    if let Some(remote_id) = remote_id(resource) {
+        return handle_adopt(kube_client, remote_client, resource, &remote_id).await;
+    }
+
+    let dto = R::kube_type_to_dto(resource);
+
+    if let Some(uuid) = R::dto_uuid(&dto) {
+        if remote_client.get_by_id(&uuid).await.is_ok() {
+            warn!("Remote {} already exists, skipping creation", kind);
+            return Ok(());
+        }
+    }
+
+    match remote_client.create(dto).await {
+        Ok(remote_dto) => {
+            if let Some(uuid) = R::dto_uuid(&remote_dto) {
+                kube_client.add_finalizer(resource).await?;
+                publish_event(
+                    kube_client,
+                    resource,
+                    EventType::Normal,
+                    "Created",
+                    "Create",
+                    format!("Created the remote {} `{}`", kind, uuid),
+                )
+                .await;
+                let generation = resource.meta().generation;
+                if let Some(status) = resource.status_mut().as_mut() {
+                    status.set_uuid(uuid);
+                    status.set_observed_generation(generation);
+                }
+                conditions::set(
+                    resource,
+                    SYNCED_CONDITION,
+                    true,
+                    "Created",
+                    format!("The remote {} was created", kind),
+                );
+                kube_client.update_status(resource).await
+            } else {
+                warn!("Remote {} has no uuid, cannot update status", kind);
+                Ok(())
+            }
+        }
+        Err(e) => {
+            error!("Failed to create a new {}: {:?}", kind, e);
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "CreateFailed",
+                "Create",
+                format!("Failed to create the remote {}: {}", kind, e),
+            )
+            .await;
+            conditions::set(
+                resource,
+                SYNCED_CONDITION,
+                false,
+                "CreateFailed",
+                format!("Failed to create the remote {}", kind),
+            );
+            conditions::set_error(resource, &e);
+            kube_client.update_status(resource).await?;
+            Err(e)
+        }
+    }
+}
+
+/// Brings an existing remote resource under management instead of creating a new one:
+/// verifies that it exists, records its ID and adds the finalizer.
+/// Differences between the remote resource and the spec are resolved afterwards
+/// by [`check_for_drift`], according to the drift policy.
+pub async fn handle_adopt<T, R>(
+    kube_client: &dyn KubeApi<T>,
+    remote_client: &R,
+    resource: &mut T,
+    remote_id: &str,
+) -> Result<(), OperatorError>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    let kind = T::kind(&());
+    let remote_dto = match remote_client.get_by_id(remote_id).await {
+        Ok(remote_dto) => remote_dto,
+        Err(e) => {
+            error!(
+                "Failed to get the {} `{}` to adopt: {:?}",
+                kind, remote_id, e
+            );
+            publish_event(
+                kube_client,
+                resource,
+                EventType::Warning,
+                "AdoptFailed",
+                "Adopt",
+                format!(
+                    "Failed to adopt the remote {} `{}` referenced by {}: {}",
+                    kind, remote_id, REMOTE_ID_ANNOTATION, e
+                ),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+    let uuid = R::dto_uuid(&remote_dto).unwrap_or_else(|| remote_id.to_string());
+
+    kube_client.add_finalizer(resource).await?;
+    publish_event(
+        kube_client,
+        resource,
+        EventType::Normal,
+        "Adopted",
+        "Adopt",
+        format!("Adopted the existing remote {} `{}`", kind, uuid),
+    )
+    .await;
+    let generation = resource.meta().generation;
+    if let Some(status) = resource.status_mut().as_mut() {
+        status.set_uuid(uuid);
+        status.set_observed_generation(generation);
+    }
+    conditions::set(
+        resource,
+        SYNCED_CONDITION,
+        true,
+        "Adopted",
+        format!("The existing remote {} was adopted", kind),
+    );
+    kube_client.update_status(resource).await?;
+    info!("Adopted an existing {}", kind);
+    Ok(())
+}
+
+/// Returns the ID of the remote resource to adopt, if the resource references one.
+fn remote_id<T>(resource: &T) -> Option<String>
+where
+    T: SyncedResource,
+{
+    resource
+        .meta()
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(REMOTE_ID_ANNOTATION))
+        .map(|remote_id| remote_id.trim().to_string())
+        .filter(|remote_id| !remote_id.is_empty())
+}
+
+/// Publishes a Kubernetes Event regarding the resource.
+/// Failing to publish an event is logged but never fails the reconciliation.
+async fn publish_event<T>(
+    kube_client: &dyn KubeApi<T>,
+    resource: &T,
+    type_: EventType,
+    reason: &str,
+    action: &str,
+    note: String,
+) where
+    T: SyncedResource,
+{
+    let event = Event {
+        type_,
+        reason: reason.to_string(),
+        note: Some(note),
+        action: action.to_string(),
+        secondary: None,
+    };
+
+    if let Err(e) = kube_client.publish_event(resource, event).await {
+        warn!("Failed to publish event `{}`: {:?}", reason, e);
+    }
+}
+
+/// Provides utility functions shared by the per-kind converters.
+pub mod converters {
+    pub fn uuid_to_string(uuid: Option<uuid::Uuid>) -> Option<String> {
+        uuid.map(|uuid| uuid.to_string())
+    }
+
+    pub fn string_to_uuid(uuid: Option<String>) -> Option<uuid::Uuid> {
+        uuid.and_then(|uuid| uuid::Uuid::parse_str(&uuid).ok())
+    }
+}
@@ -1,4 +1,6 @@
 // This file is generated by kopgen. Do not edit manually. If you need to make adjustments add it to .openapi-generator-ignore file.
+use std::fmt::Debug;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -37,4 +39,137 @@ pub enum OperatorError {
     InvalidResourceState(String),
     #[error("Failed to deploy CRD: {0}")]
     FailedToDeployCRD(#[source] anyhow::Error),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl OperatorError {
+    /// Returns the name of the variant, used to label error metrics.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            OperatorError::AnyhowError(_) => "AnyhowError",
+            OperatorError::FailedToAddEvent(_) => "FailedToAddEvent",
+            OperatorError::FailedToCreateClient(_) => "FailedToCreateClient",
+            OperatorError::FailedToDeleteResource(_) => "FailedToDeleteResource",
+            OperatorError::FailedToUpdateStatus(_) => "FailedToUpdateStatus",
+            OperatorError::FailedToCreateResource(_) => "FailedToCreateResource",
+            OperatorError::FailedToUpdateResource(_) => "FailedToUpdateResource",
+            OperatorError::FailedToGetResource(_) => "FailedToGetResource",
+            OperatorError::FailedToListResources(_) => "FailedToListResources",
+            OperatorError::FailedToPatchResource(_) => "FailedToPatchResource",
+            OperatorError::FailedToReplaceResource(_) => "FailedToReplaceResource",
+            OperatorError::FailedToWatchResources(_) => "FailedToWatchResources",
+            OperatorError::FailedToReconcileResource(_) => "FailedToReconcileResource",
+            OperatorError::ResourceNotFound(_) => "ResourceNotFound",
+            OperatorError::InvalidResource(_) => "InvalidResource",
+            OperatorError::InvalidResourceState(_) => "InvalidResourceState",
+            OperatorError::FailedToDeployCRD(_) => "FailedToDeployCRD",
+            OperatorError::InvalidConfig(_) => "InvalidConfig",
+        }
+    }
+}
+
+/// How the controller should react to an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// A transient failure, e.g. a timeout or a server error, that is retried with backoff.
+    Retryable,
+    /// A failure that retrying won't fix, e.g. a rejected request, until the resource changes.
+    Permanent,
+    /// The resource was modified concurrently, retried soon after reading it again.
+    Conflict,
+    /// The resource does not exist.
+    NotFound,
+    /// The credentials were rejected.
+    Unauthorized,
+}
+
+impl ErrorClass {
+    /// Classifies an HTTP status code returned by the remote API or the Kubernetes API.
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => ErrorClass::Unauthorized,
+            404 => ErrorClass::NotFound,
+            409 => ErrorClass::Conflict,
+            408 | 425 | 429 => ErrorClass::Retryable,
+            400..=499 => ErrorClass::Permanent,
+            _ => ErrorClass::Retryable,
+        }
+    }
+}
+
+/// An error returned by the remote API, keeping the HTTP status code of the response
+/// so the error can be classified.
+#[derive(Debug, Error)]
+#[error("{source}")]
+pub struct RemoteApiError {
+    pub status: Option<u16>,
+    #[source]
+    source: anyhow::Error,
+}
+
+impl<T> From<openapi::apis::Error<T>> for RemoteApiError
+where
+    T: Debug + Send + Sync + 'static,
+{
+    fn from(error: openapi::apis::Error<T>) -> Self {
+        let status = match &error {
+            openapi::apis::Error::ResponseError(response) => Some(response.status.as_u16()),
+            openapi::apis::Error::Reqwest(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        };
+        Self {
+            status,
+            source: error.into(),
+        }
+    }
+}
+
+impl OperatorError {
+    /// Classifies the error, based on the status code of the remote API or Kubernetes API
+    /// response that caused it.
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            OperatorError::ResourceNotFound(_) => ErrorClass::NotFound,
+            OperatorError::InvalidResource(_)
+            | OperatorError::InvalidResourceState(_)
+            | OperatorError::InvalidConfig(_) => ErrorClass::Permanent,
+            _ => self
+                .status_code()
+                .map_or(ErrorClass::Retryable, ErrorClass::from_status),
+        }
+    }
+
+    /// Returns the HTTP status code of the remote API or Kubernetes API response
+    /// that caused the error, if there was a response.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            OperatorError::ResourceNotFound(_)
+            | OperatorError::InvalidResource(_)
+            | OperatorError::InvalidResourceState(_)
+            | OperatorError::InvalidConfig(_) => None,
+            OperatorError::AnyhowError(e)
+            | OperatorError::FailedToAddEvent(e)
+            | OperatorError::FailedToCreateClient(e)
+            | OperatorError::FailedToDeleteResource(e)
+            | OperatorError::FailedToUpdateStatus(e)
+            | OperatorError::FailedToCreateResource(e)
+            | OperatorError::FailedToUpdateResource(e)
+            | OperatorError::FailedToGetResource(e)
+            | OperatorError::FailedToListResources(e)
+            | OperatorError::FailedToPatchResource(e)
+            | OperatorError::FailedToReplaceResource(e)
+            | OperatorError::FailedToWatchResources(e)
+            | OperatorError::FailedToReconcileResource(e)
+            | OperatorError::FailedToDeployCRD(e) => {
+                if let Some(remote_error) = e.downcast_ref::<RemoteApiError>() {
+                    return remote_error.status;
+                }
+                match e.downcast_ref::<kube::Error>() {
+                    Some(kube::Error::Api(response)) => Some(response.code),
+                    _ => None,
+                }
+            }
+        }
+    }
 }
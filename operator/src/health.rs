@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock, Mutex,
+    },
+    time::Duration,
+};
+
+use log::{info, warn};
+use openapi::apis::configuration::Configuration;
+use tokio::time::sleep;
+
+use crate::reload::Reloadable;
+
+/// The health of the operator, shared by the controllers and the health server.
+pub static HEALTH: LazyLock<Health> = LazyLock::new(Health::default);
+
+/// The state of a single controller, keyed by the URL of the resources it watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControllerState {
+    /// The controller is running, but its watch has not listed the resources yet.
+    Starting,
+    /// The controller is running and its watch stream is live.
+    Watching,
+    /// The controller is running, but its watch stream is failing.
+    WatchFailing,
+    /// The controller has exited and no longer reconciles resources.
+    Stopped,
+}
+
+/// Tracks what the liveness and readiness probes report.
+///
+/// - Liveness fails as soon as a controller has exited.
+/// - Readiness requires the CRDs to be established, every controller watch to be live
+///   and, when the remote API check is enabled, the remote API to be reachable.
+#[derive(Debug, Default)]
+pub struct Health {
+    crds_established: AtomicBool,
+    remote_api_check_enabled: AtomicBool,
+    remote_api_reachable: AtomicBool,
+    controllers: Mutex<BTreeMap<String, ControllerState>>,
+}
+
+impl Health {
+    /// Records whether all CRDs the controllers rely on are established.
+    pub fn set_crds_established(&self, established: bool) {
+        self.crds_established.store(established, Ordering::Relaxed);
+    }
+
+    /// Makes readiness depend on the remote API being reachable.
+    pub fn enable_remote_api_check(&self) {
+        self.remote_api_check_enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Records whether the last probe reached the remote API.
+    pub fn set_remote_api_reachable(&self, reachable: bool) {
+        self.remote_api_reachable
+            .store(reachable, Ordering::Relaxed);
+    }
+
+    /// Records the state of a controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `controller` - The URL of the resources the controller watches.
+    /// * `state` - The new state of the controller.
+    pub fn set_controller_state(&self, controller: &str, state: ControllerState) {
+        let mut controllers = self.controllers.lock().unwrap_or_else(|e| e.into_inner());
+        // A stopped controller stays stopped until it is removed.
+        if controllers.get(controller) == Some(&ControllerState::Stopped) {
+            return;
+        }
+        controllers.insert(controller.to_string(), state);
+    }
+
+    /// Forgets all controllers, e.g. when they are stopped on purpose after losing leadership.
+    pub fn clear_controllers(&self) {
+        self.controllers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Checks whether the operator is alive. Returns the reason when it is not.
+    pub fn liveness(&self) -> Result<(), String> {
+        let stopped = self.controllers_in(&[ControllerState::Stopped]);
+        if !stopped.is_empty() {
+            return Err(format!("Controllers have stopped: {}", stopped.join(", ")));
+        }
+        Ok(())
+    }
+
+    /// Checks whether the operator is ready. Returns the reason when it is not.
+    pub fn readiness(&self) -> Result<(), String> {
+        self.liveness()?;
+
+        if !self.crds_established.load(Ordering::Relaxed) {
+            return Err("CRDs are not established yet".to_string());
+        }
+
+        let not_watching =
+            self.controllers_in(&[ControllerState::Starting, ControllerState::WatchFailing]);
+        if !not_watching.is_empty() {
+            return Err(format!(
+                "Controller watches are not live: {}",
+                not_watching.join(", ")
+            ));
+        }
+
+        if self.remote_api_check_enabled.load(Ordering::Relaxed)
+            && !self.remote_api_reachable.load(Ordering::Relaxed)
+        {
+            return Err("Remote API is not reachable".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn controllers_in(&self, states: &[ControllerState]) -> Vec<String> {
+        self.controllers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, state)| states.contains(state))
+            .map(|(controller, _)| controller.clone())
+            .collect()
+    }
+}
+
+/// Periodically checks whether the remote API answers, any HTTP response counts as reachable.
+///
+/// # Arguments
+///
+/// * `config` - The configuration of the remote API client, the latest one is used for every probe.
+/// * `interval` - The time between two probes.
+pub async fn probe_remote_api(config: &Reloadable<Configuration>, interval: Duration) {
+    let mut reachable = None;
+    loop {
+        let config = config.get();
+        let is_reachable = match config.client.get(&config.base_path).send().await {
+            Ok(_) => true,
+            Err(e) => {
+                warn!(
+                    "Remote API `{}` is not reachable: {:?}",
+                    config.base_path, e
+                );
+                false
+            }
+        };
+        if is_reachable && reachable != Some(true) {
+            info!("Remote API `{}` is reachable", config.base_path);
+        }
+        reachable = Some(is_reachable);
+        HEALTH.set_remote_api_reachable(is_reachable);
+        sleep(interval).await;
+    }
+}
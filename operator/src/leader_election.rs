@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::MicroTime,
+};
+use kube::{
+    api::{Api, ObjectMeta, PostParams},
+    Client, Error,
+};
+use log::{debug, info, warn};
+use std::time::{Duration, Instant};
+use tokio::time::{sleep, timeout};
+
+use crate::errors::OperatorError;
+
+/// Coordinates multiple operator replicas through a `coordination.k8s.io/v1` Lease,
+/// so that only the replica holding the lease runs the controllers.
+pub struct LeaderElector {
+    api: Api<Lease>,
+    lease_name: String,
+    identity: String,
+    lease_duration: Duration,
+    renew_deadline: Duration,
+    retry_period: Duration,
+}
+
+impl LeaderElector {
+    /// Creates a new leader elector.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The Kubernetes client.
+    /// * `namespace` - The namespace the Lease lives in, usually the operator's own namespace.
+    /// * `lease_name` - The name of the Lease shared by all replicas.
+    /// * `identity` - The unique identity of this replica, usually the pod name.
+    /// * `lease_duration` - How long a lease is valid without being renewed.
+    pub fn new(
+        client: Client,
+        namespace: &str,
+        lease_name: &str,
+        identity: &str,
+        lease_duration: Duration,
+    ) -> Self {
+        Self {
+            api: Api::namespaced(client, namespace),
+            lease_name: lease_name.to_string(),
+            identity: identity.to_string(),
+            lease_duration,
+            renew_deadline: lease_duration * 2 / 3,
+            retry_period: lease_duration / 5,
+        }
+    }
+
+    /// Returns the identity of this replica.
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// Waits until this replica holds the lease.
+    pub async fn acquire(&self) {
+        loop {
+            match self.try_acquire_or_renew().await {
+                Ok(true) => {
+                    info!(
+                        "Acquired lease `{}` as `{}`, this replica is now the leader.",
+                        self.lease_name, self.identity
+                    );
+                    return;
+                }
+                Ok(false) => debug!(
+                    "Lease `{}` is held by another replica, standing by.",
+                    self.lease_name
+                ),
+                Err(e) => warn!("Failed to acquire lease `{}`: {:?}", self.lease_name, e),
+            }
+            sleep(self.retry_period).await;
+        }
+    }
+
+    /// Keeps renewing the lease and returns as soon as leadership is lost.
+    ///
+    /// Leadership is considered lost when another replica took over the lease,
+    /// or when the lease could not be renewed within the renew deadline, which is
+    /// shorter than the lease duration so the old leader steps down before a
+    /// standby replica may take over. A renewal that hangs, e.g. because the API server
+    /// is unreachable, is given up once the renew deadline has passed.
+    pub async fn hold(&self) {
+        let mut last_renewal = Instant::now();
+        loop {
+            sleep(self.retry_period).await;
+            let remaining = self.renew_deadline.saturating_sub(last_renewal.elapsed());
+            match timeout(remaining, self.try_acquire_or_renew()).await {
+                Ok(Ok(true)) => last_renewal = Instant::now(),
+                Ok(Ok(false)) => {
+                    warn!(
+                        "Lease `{}` has been taken over by another replica.",
+                        self.lease_name
+                    );
+                    return;
+                }
+                Ok(Err(e)) => {
+                    warn!("Failed to renew lease `{}`: {:?}", self.lease_name, e);
+                    if last_renewal.elapsed() >= self.renew_deadline {
+                        warn!(
+                            "Lease `{}` was not renewed within {:?}, stepping down.",
+                            self.lease_name, self.renew_deadline
+                        );
+                        return;
+                    }
+                }
+                Err(_) => {
+                    warn!(
+                        "Renewing lease `{}` did not finish within {:?}, stepping down.",
+                        self.lease_name, self.renew_deadline
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Returns how long the controllers may keep running after leadership was lost.
+    ///
+    /// [`hold`](Self::hold) notices a missed renewal at most one retry period after the
+    /// renew deadline, even when a renewal hangs, so a standby replica cannot take over
+    /// the lease before this much time has passed.
+    pub fn step_down_grace_period(&self) -> Duration {
+        self.lease_duration
+            .saturating_sub(self.renew_deadline)
+            .saturating_sub(self.retry_period)
+    }
+
+    /// Gives up the lease if this replica holds it,
+    /// so that a standby replica can take over without waiting for it to expire.
+    pub async fn release(&self) -> Result<(), OperatorError> {
+        let mut lease = match self
+            .api
+            .get_opt(&self.lease_name)
+            .await
+            .map_err(|e| OperatorError::FailedToGetResource(e.into()))?
+        {
+            Some(lease) => lease,
+            None => return Ok(()),
+        };
+
+        let spec = lease.spec.get_or_insert_with(LeaseSpec::default);
+        if spec.holder_identity.as_deref() != Some(self.identity.as_str()) {
+            return Ok(());
+        }
+
+        spec.holder_identity = None;
+        spec.lease_duration_seconds = Some(1);
+        spec.renew_time = Some(MicroTime(Utc::now()));
+
+        self.api
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await
+            .map_err(|e| OperatorError::FailedToReplaceResource(e.into()))?;
+
+        info!("Released lease `{}`.", self.lease_name);
+        Ok(())
+    }
+
+    /// Tries to acquire the lease, or renews it if this replica already holds it.
+    /// Returns whether this replica holds the lease afterwards.
+    pub async fn try_acquire_or_renew(&self) -> Result<bool, OperatorError> {
+        let now = Utc::now();
+        let lease_duration_seconds = self.lease_duration.as_secs() as i32;
+
+        let existing = self
+            .api
+            .get_opt(&self.lease_name)
+            .await
+            .map_err(|e| OperatorError::FailedToGetResource(e.into()))?;
+
+        let mut lease = match existing {
+            Some(lease) => lease,
+            None => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.lease_name.clone()),
+                        ..Default::default()
+                    },
+                    spec: Some(LeaseSpec {
+                        holder_identity: Some(self.identity.clone()),
+                        lease_duration_seconds: Some(lease_duration_seconds),
+                        acquire_time: Some(MicroTime(now)),
+                        renew_time: Some(MicroTime(now)),
+                        lease_transitions: Some(0),
+                    }),
+                };
+                return match self.api.create(&PostParams::default(), &lease).await {
+                    Ok(_) => Ok(true),
+                    Err(Error::Api(ae)) if ae.code == 409 => Ok(false),
+                    Err(e) => Err(OperatorError::FailedToCreateResource(e.into())),
+                };
+            }
+        };
+
+        let spec = lease.spec.get_or_insert_with(LeaseSpec::default);
+        let is_holder = spec.holder_identity.as_deref() == Some(self.identity.as_str());
+
+        if !is_holder && !is_lease_expired(spec, now) {
+            return Ok(false);
+        }
+
+        if !is_holder {
+            spec.holder_identity = Some(self.identity.clone());
+            spec.acquire_time = Some(MicroTime(now));
+            spec.lease_transitions = Some(spec.lease_transitions.unwrap_or_default() + 1);
+        }
+        spec.renew_time = Some(MicroTime(now));
+        spec.lease_duration_seconds = Some(lease_duration_seconds);
+
+        // The resourceVersion from the read above makes the replace fail with a conflict
+        // if another replica updated the lease in the meantime.
+        match self
+            .api
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(Error::Api(ae)) if ae.code == 409 => Ok(false),
+            Err(e) => Err(OperatorError::FailedToReplaceResource(e.into())),
+        }
+    }
+}
+
+/// Checks whether a lease can be taken over, either because nobody holds it
+/// or because its holder did not renew it within the lease duration.
+pub fn is_lease_expired(spec: &LeaseSpec, now: DateTime<Utc>) -> bool {
+    if spec
+        .holder_identity
+        .as_deref()
+        .unwrap_or_default()
+        .is_empty()
+    {
+        return true;
+    }
+
+    match (spec.renew_time.as_ref(), spec.lease_duration_seconds) {
+        (Some(renew_time), Some(duration)) => {
+            renew_time.0 + chrono::Duration::seconds(duration.into()) < now
+        }
+        _ => true,
+    }
+}
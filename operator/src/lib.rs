@@ -1,19 +1,26 @@
 pub mod cli;
+pub mod config;
 pub mod controllers;
 pub mod errors;
+pub mod health;
+pub mod leader_election;
+pub mod metrics;
+pub mod reload;
 pub mod types;
 
 use crate::errors::OperatorError;
 use async_trait::async_trait;
+use k8s_openapi::api::core::v1::ConfigMap;
 use kube::{
-    api::{Api, Patch, PatchParams, PostParams, Resource},
-    core::object::HasStatus,
+    api::{Api, ListParams, ObjectMeta, Patch, PatchParams, PostParams, Resource},
+    core::{object::HasStatus, NamespaceResourceScope},
+    runtime::events::{Event, Recorder, Reporter},
     Error,
 };
 use log::{debug, error, info, warn};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 use tokio::time::{sleep, Duration};
 
 pub const FINALIZER_DOMAIN: &str = "example.com";
@@ -28,22 +35,58 @@ where
 
     async fn remove_finalizer(&self, resource: &mut T) -> Result<(), OperatorError>;
 
-    async fn update_status(&self, status: &T) -> Result<(), OperatorError>;
+    /// Writes the status of the resource. The resource takes over the resource version of the
+    /// written object, so it can be used as a precondition of a later patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The resource whose status is written.
+    async fn update_status(&self, resource: &mut T) -> Result<(), OperatorError>;
 
-    /// Replaces the specified resource in Kubernetes.
+    /// Lists the resources in the scope of the client, i.e. a namespace or the whole cluster.
+    async fn list(&self) -> Result<Vec<T>, OperatorError>;
+
+    /// Creates the resource in its namespace.
     ///
     /// # Arguments
     ///
-    /// * `name` - The name of the resource to replace.
-    /// * `post_params` - Parameters for the replace operation.
-    /// * `resource` - The new state of the resource.
-    async fn replace(
+    /// * `resource` - The resource to create.
+    async fn create(&self, resource: &T) -> Result<T, OperatorError>;
+
+    /// Patches the specified resource in Kubernetes, changing only the fields in the patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The resource to patch.
+    /// * `patch_params` - Parameters for the patch operation.
+    /// * `patch` - The patch, e.g. a JSON merge patch or a server-side apply patch.
+    async fn patch(
         &self,
-        name: &str,
-        post_params: &PostParams,
         resource: &T,
+        patch_params: &PatchParams,
+        patch: &Patch<serde_json::Value>,
     ) -> Result<T, OperatorError>;
 
+    /// Publishes a Kubernetes Event regarding the specified resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The resource the event is about.
+    /// * `event` - The event to publish.
+    async fn publish_event(&self, resource: &T, event: Event) -> Result<(), OperatorError>;
+
+    /// Lists the IDs of the remote resources the operator has released, i.e. left behind
+    /// because of a deletion policy or tagged as orphans, which no resource is meant to reference.
+    async fn released_ids(&self) -> Result<HashSet<String>, OperatorError>;
+
+    /// Records that the remote resource with the given ID has been released.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The ID of the remote resource.
+    /// * `reason` - Why it was released, e.g. the deletion policy.
+    async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+
     fn get_client(&self) -> Api<T>;
 
     fn set_client(&mut self, client: Api<T>);
@@ -55,12 +98,51 @@ where
     T::Status: Serialize,
 {
     pub client: Api<T>,
+    /// The ConfigMaps in the namespace of the operator, which record the released remote IDs.
+    pub released_ids: Api<ConfigMap>,
+}
+
+impl<T> KubeApiClient<T>
+where
+    T: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + Clone
+        + Send
+        + Sync
+        + 'static
+        + DeserializeOwned
+        + Serialize
+        + Debug
+        + HasStatus,
+    T::Status: Serialize,
+{
+    /// Returns a client scoped to the namespace of the given resource,
+    /// so that calls work regardless of whether the watch is cluster-wide or namespaced.
+    fn api_for(&self, resource: &T) -> Api<T> {
+        match resource.meta().namespace.as_deref() {
+            Some(namespace) => Api::namespaced(self.get_client().into_client(), namespace),
+            None => self.get_client(),
+        }
+    }
+
+    /// Returns the name of the ConfigMap recording the released remote IDs of the kind,
+    /// e.g. `operator-released-cats`.
+    fn released_ids_name() -> String {
+        format!("{}-released-{}", FIELD_MANAGER, T::plural(&()))
+    }
 }
 
 #[async_trait]
 impl<T> KubeApi<T> for KubeApiClient<T>
 where
-    T: Resource + Clone + Send + Sync + 'static + DeserializeOwned + Serialize + Debug + HasStatus,
+    T: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + Clone
+        + Send
+        + Sync
+        + 'static
+        + DeserializeOwned
+        + Serialize
+        + Debug
+        + HasStatus,
     T::Status: Serialize,
 {
     async fn add_finalizer(&self, resource: &mut T) -> Result<(), OperatorError> {
@@ -81,7 +163,8 @@ where
         let patch = Patch::Merge(json!({ "metadata": { "finalizers": finalizers } }));
         let patch_params = PatchParams::apply(FIELD_MANAGER);
 
-        self.get_client()
+        let patched = self
+            .api_for(resource)
             .patch(&resource_name, &patch_params, &patch)
             .await
             .map_err(|e| {
@@ -91,6 +174,7 @@ where
                 );
                 OperatorError::FailedToPatchResource(e.into())
             })?;
+        resource.meta_mut().resource_version = patched.meta().resource_version.clone();
 
         Ok(())
     }
@@ -115,7 +199,7 @@ where
         let patch = Patch::Merge(json!({ "metadata": { "finalizers": finalizers } }));
         let patch_params = PatchParams::apply(FIELD_MANAGER);
 
-        self.get_client()
+        self.api_for(resource)
             .patch(&resource_name, &patch_params, &patch)
             .await
             .map_err(|e| {
@@ -129,12 +213,12 @@ where
         Ok(())
     }
 
-    async fn update_status(&self, status: &T) -> Result<(), OperatorError> {
-        let resource_name = status.meta().name.clone().ok_or_else(|| {
+    async fn update_status(&self, resource: &mut T) -> Result<(), OperatorError> {
+        let resource_name = resource.meta().name.clone().ok_or_else(|| {
             OperatorError::InvalidResourceState("Resource name is missing.".into())
         })?;
 
-        let status_patch = if let Some(status) = status.status() {
+        let status_patch = if let Some(status) = resource.status() {
             json!({ "status": status })
         } else {
             json!({ "status": null })
@@ -145,11 +229,12 @@ where
 
         for _ in 0..3 {
             match self
-                .get_client()
+                .api_for(resource)
                 .patch_status(&resource_name, &patch_params, &patch)
                 .await
             {
-                Ok(_) => {
+                Ok(updated) => {
+                    resource.meta_mut().resource_version = updated.meta().resource_version.clone();
                     info!(
                         "Successfully updated status for resource `{}`.",
                         resource_name
@@ -175,18 +260,134 @@ where
         )))
     }
 
-    async fn replace(
+    async fn list(&self) -> Result<Vec<T>, OperatorError> {
+        self.get_client()
+            .list(&ListParams::default())
+            .await
+            .map(|list| list.items)
+            .map_err(|e| {
+                error!("Failed to list resources: {:?}", e);
+                OperatorError::FailedToListResources(e.into())
+            })
+    }
+
+    async fn create(&self, resource: &T) -> Result<T, OperatorError> {
+        self.api_for(resource)
+            .create(&PostParams::default(), resource)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to create resource `{}`: {:?}",
+                    resource.meta().name.as_deref().unwrap_or_default(),
+                    e
+                );
+                OperatorError::FailedToCreateResource(e.into())
+            })
+    }
+
+    async fn patch(
         &self,
-        name: &str,
-        post_params: &PostParams,
         resource: &T,
+        patch_params: &PatchParams,
+        patch: &Patch<serde_json::Value>,
     ) -> Result<T, OperatorError> {
-        self.client
-            .replace(name, post_params, &resource.clone())
+        let resource_name = resource.meta().name.clone().ok_or_else(|| {
+            OperatorError::InvalidResourceState("Resource name is missing.".into())
+        })?;
+
+        self.api_for(resource)
+            .patch(&resource_name, patch_params, patch)
             .await
             .map_err(|e| {
-                error!("Failed to replace resource `{}`: {:?}", name, e);
-                OperatorError::FailedToUpdateResource(e.into())
+                error!("Failed to patch resource `{}`: {:?}", resource_name, e);
+                OperatorError::FailedToPatchResource(e.into())
+            })
+    }
+
+    async fn publish_event(&self, resource: &T, event: Event) -> Result<(), OperatorError> {
+        let reporter = Reporter {
+            controller: FIELD_MANAGER.to_string(),
+            instance: std::env::var("POD_NAME").ok(),
+        };
+        let recorder = Recorder::new(
+            self.get_client().into_client(),
+            reporter,
+            resource.object_ref(&()),
+        );
+
+        recorder.publish(event).await.map_err(|e| {
+            error!(
+                "Failed to publish event for resource `{}`: {:?}",
+                resource.meta().name.as_deref().unwrap_or_default(),
+                e
+            );
+            OperatorError::FailedToAddEvent(e.into())
+        })
+    }
+
+    async fn released_ids(&self) -> Result<HashSet<String>, OperatorError> {
+        let name = Self::released_ids_name();
+        let config_map = self.released_ids.get_opt(&name).await.map_err(|e| {
+            error!("Failed to get the released remote IDs `{}`: {:?}", name, e);
+            OperatorError::FailedToGetResource(e.into())
+        })?;
+
+        Ok(config_map
+            .and_then(|config_map| config_map.data)
+            .map(|data| data.into_keys().collect())
+            .unwrap_or_default())
+    }
+
+    async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError> {
+        let name = Self::released_ids_name();
+        let patch = Patch::Merge(json!({ "data": { uuid: reason } }));
+
+        match self
+            .released_ids
+            .patch(&name, &PatchParams::default(), &patch)
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(Error::Api(ae)) if ae.code == 404 => {}
+            Err(e) => {
+                error!(
+                    "Failed to record the released remote ID `{}`: {:?}",
+                    uuid, e
+                );
+                return Err(OperatorError::FailedToPatchResource(e.into()));
+            }
+        }
+
+        let config_map = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name),
+                labels: Some(
+                    [(
+                        "app.kubernetes.io/managed-by".to_string(),
+                        FIELD_MANAGER.to_string(),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            },
+            data: Some(
+                [(uuid.to_string(), reason.to_string())]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        self.released_ids
+            .create(&PostParams::default(), &config_map)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                error!(
+                    "Failed to record the released remote ID `{}`: {:?}",
+                    uuid, e
+                );
+                OperatorError::FailedToCreateResource(e.into())
             })
     }
 
@@ -2,35 +2,115 @@
 use anyhow::Context;
 use clap::Parser;
 use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
-use kube::{api::Api, Client as KubeClient, CustomResourceExt};
-use log::{debug, error, info, warn};
+use kube::{
+    api::Api,
+    core::{object::HasStatus, NamespaceResourceScope},
+    Client as KubeClient, CustomResourceExt, Resource,
+};
+use log::{debug, error, info, warn, LevelFilter};
 use operator::{
-    cli::{Cli, Commands},
+    cli::{Cli, Commands, WatchScope},
+    config::{ControllerSettings, ImportConfig, OperatorConfig, OrphansConfig},
+    health::{probe_remote_api, HEALTH},
+    leader_election::LeaderElector,
+    metrics::METRICS,
+    reload::{watch_config, Reloadable},
     KubeApi, KubeApiClient,
 };
-use std::sync::Arc;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, sync::Arc, time::Duration};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task::JoinHandle,
+    time::timeout,
+};
+use tokio_util::sync::CancellationToken;
 use warp::Filter;
 
 use operator::{
-    controllers::cats,
+    controllers::{cats, dogs, horses, importer, orphans},
     types::{cat::Cat, dog::Dog, horse::Horse},
 };
 
-use openapi::apis::{cats_api::CatsApiClient, configuration::Configuration};
+use openapi::apis::{
+    cats_api::{CatsApi, CatsApiClient},
+    configuration::Configuration,
+    dogs_api::{DogsApi, DogsApiClient},
+    horses_api::{HorsesApi, HorsesApiClient},
+};
+
+/// How often the remote API is probed when readiness depends on it.
+const REMOTE_API_PROBE_INTERVAL_IN_SEC: u64 = 10;
+
+/// How often the configuration file is checked for changes when it is watched.
+const CONFIG_RELOAD_INTERVAL_IN_SEC: u64 = 10;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    env_logger::init();
-
     let cli = Cli::parse();
 
+    let log_level_reloadable = init_logger(&cli.verbosity);
+
     debug!("Log level: {}", cli.verbosity);
 
     match cli.command {
-        Some(Commands::Run { install_crds }) => {
+        Some(Commands::Run {
+            install_crds,
+            config: config_overrides,
+            watch_config: watch_config_file,
+            leader_election,
+            leader_election_lease_name,
+            leader_election_namespace,
+            operator_namespace,
+            leader_election_identity,
+            leader_election_lease_duration,
+            check_remote_api,
+            shutdown_grace_period,
+        }) => {
             info!("Starting operator...");
             debug!("CRD's will be installed automatically: {}", install_crds);
 
+            let operator_config = config_overrides
+                .load()
+                .context("Failed to load the operator configuration")?;
+            debug!("Operator configuration: {:?}", operator_config);
+            if let Some(level) = operator_config.log_level() {
+                set_log_level(level, log_level_reloadable);
+            }
+
+            let watch_scope = WatchScope::new(
+                operator_config.watch.all_namespaces,
+                &operator_config.watch.namespaces,
+            );
+            info!("Watching resources in {:?}", watch_scope);
+
+            let bind_address = operator_config.server.bind_address;
+            let server_shutdown = CancellationToken::new();
+            let server_stopped = server_shutdown.clone();
+            let health_server = tokio::spawn(async move {
+                let liveness_route = warp::path!("healthz").map(|| health_reply(HEALTH.liveness()));
+
+                let readiness_route =
+                    warp::path!("readyz").map(|| health_reply(HEALTH.readiness()));
+
+                let metrics_route = warp::path!("metrics").map(|| {
+                    warp::reply::with_header(
+                        METRICS.render(),
+                        "Content-Type",
+                        "text/plain; version=0.0.4",
+                    )
+                });
+
+                let health_routes = liveness_route.or(readiness_route).or(metrics_route);
+
+                let (_, server) = warp::serve(health_routes)
+                    .bind_with_graceful_shutdown(bind_address, server_stopped.cancelled_owned());
+                server.await;
+            });
+
+            let shutdown = CancellationToken::new();
+            let grace_period = Duration::from_secs(shutdown_grace_period);
+
             let kube_client = KubeClient::try_default().await?;
             let crd_api: Api<CustomResourceDefinition> = Api::all(kube_client.clone());
 
@@ -56,7 +136,12 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            let controllers_crds = vec![format!("cats.example.com")];
+            let controllers_crds = vec![
+                format!("cats.example.com"),
+                format!("dogs.example.com"),
+                format!("horses.example.com"),
+            ];
+            let mut crds_established = true;
             for controller_crd in controllers_crds {
                 if let Err(e) = kube::runtime::wait::await_condition(
                     crd_api.clone(),
@@ -66,37 +151,136 @@ async fn main() -> anyhow::Result<()> {
                 .await
                 {
                     error!("Error waiting for CRD {}: {}", &controller_crd, e);
+                    crds_established = false;
                 }
             }
+            HEALTH.set_crds_established(crds_established);
 
-            let config = Arc::new(Configuration {
-                base_path: std::env::var("API_URL").unwrap_or_default(),
-                client: reqwest::Client::new(),
-                user_agent: Some(std::env::var("HTTP_USER_AGENT").unwrap_or_default()),
-                bearer_access_token: Some(std::env::var("ACCESS_TOKEN").unwrap_or_default()),
-                ..Default::default()
-            });
+            let state = Arc::new(ReloadableState::new(
+                &operator_config,
+                log_level_reloadable,
+            )?);
 
-            // Start the cats controller for the cats.example.com/v1 API group
-            let cats_client = Arc::new(CatsApiClient::new(config));
-            let kube_cats_client: Arc<dyn KubeApi<Cat> + Send + Sync> = Arc::new(KubeApiClient {
-                client: Api::namespaced(kube_client.clone(), "default"),
-            });
-            tokio::spawn(async move {
-                let _cats_controller = cats::handle(kube_cats_client, cats_client).await;
-            });
+            if check_remote_api {
+                HEALTH.enable_remote_api_check();
+                let remote_api = state.remote_api.clone();
+                tokio::spawn(async move {
+                    probe_remote_api(
+                        &remote_api,
+                        Duration::from_secs(REMOTE_API_PROBE_INTERVAL_IN_SEC),
+                    )
+                    .await;
+                });
+            }
 
-            tokio::spawn(async {
-                let liveness_route = warp::path!("healthz")
-                    .map(|| warp::reply::with_status("OK", warp::http::StatusCode::OK));
+            if watch_config_file {
+                if config_overrides.config_file.is_some() {
+                    let state = state.clone();
+                    let current = operator_config.clone();
+                    let shutdown = shutdown.clone();
+                    tokio::spawn(async move {
+                        watch_config(
+                            &config_overrides,
+                            current,
+                            Duration::from_secs(CONFIG_RELOAD_INTERVAL_IN_SEC),
+                            shutdown,
+                            |config| {
+                                if let Err(e) = state.apply(config) {
+                                    error!("Failed to apply the changed configuration: {:?}", e);
+                                }
+                            },
+                        )
+                        .await;
+                    });
+                } else {
+                    warn!("No configuration file is set, there is nothing to watch for changes.");
+                }
+            }
 
-                let readiness_route = warp::path!("readyz")
-                    .map(|| warp::reply::with_status("OK", warp::http::StatusCode::OK));
+            if leader_election {
+                let identity =
+                    leader_election_identity.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+                let leader_elector = Arc::new(LeaderElector::new(
+                    kube_client.clone(),
+                    &leader_election_namespace,
+                    &leader_election_lease_name,
+                    &identity,
+                    Duration::from_secs(leader_election_lease_duration),
+                ));
+                info!(
+                    "Leader election enabled, waiting for lease `{}` as `{}`...",
+                    leader_election_lease_name,
+                    leader_elector.identity()
+                );
 
-                let health_routes = liveness_route.or(readiness_route);
+                let elector = leader_elector.clone();
+                let import_config = operator_config.import.clone();
+                let orphans_config = operator_config.orphans.clone();
+                let leadership_shutdown = shutdown.clone();
+                let leadership = tokio::spawn(async move {
+                    loop {
+                        tokio::select! {
+                            _ = elector.acquire() => {}
+                            _ = leadership_shutdown.cancelled() => return,
+                        }
+                        // Cancelled on its own when leadership is lost, so the running
+                        // reconciliations can finish before another replica takes over.
+                        let controllers_shutdown = leadership_shutdown.child_token();
+                        let controllers = start_controllers(
+                            &kube_client,
+                            &state,
+                            &watch_scope,
+                            &operator_namespace,
+                            &import_config,
+                            &orphans_config,
+                            &controllers_shutdown,
+                        );
+                        tokio::select! {
+                            _ = elector.hold() => {
+                                warn!("Leadership lost, stopping the controllers.");
+                                controllers_shutdown.cancel();
+                                drain_controllers(controllers, elector.step_down_grace_period())
+                                    .await;
+                                HEALTH.clear_controllers();
+                            }
+                            _ = leadership_shutdown.cancelled() => {
+                                drain_controllers(controllers, grace_period).await;
+                                return;
+                            }
+                        }
+                    }
+                });
 
-                warp::serve(health_routes).run(([0, 0, 0, 0], 8000)).await;
-            });
+                wait_for_termination().await?;
+                shutdown.cancel();
+                if let Err(e) = leadership.await {
+                    warn!("Failed to stop the controllers: {:?}", e);
+                }
+
+                if let Err(e) = leader_elector.release().await {
+                    warn!("Failed to release the leader election lease: {:?}", e);
+                }
+            } else {
+                let controllers = start_controllers(
+                    &kube_client,
+                    &state,
+                    &watch_scope,
+                    &operator_namespace,
+                    &operator_config.import,
+                    &operator_config.orphans,
+                    &shutdown,
+                );
+
+                wait_for_termination().await?;
+                shutdown.cancel();
+                drain_controllers(controllers, grace_period).await;
+            }
+
+            info!("Stopping the health server.");
+            server_shutdown.cancel();
+            if let Err(e) = health_server.await {
+                warn!("Failed to stop the health server: {:?}", e);
+            }
         }
         Some(Commands::Version) => {
             println!("Operator version: {}", env!("CARGO_PKG_VERSION"));
@@ -110,10 +294,322 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    tokio::signal::ctrl_c()
-        .await
-        .context("Failed to listen for Ctrl+C")?;
-    info!("Termination signal received. Shutting down.");
+    Ok(())
+}
+
+/// Initializes the logger from the verbosity, e.g. `debug` or `operator=debug,kube=info`.
+/// Returns whether the log level can be changed later, which is only the case for a single level.
+fn init_logger(verbosity: &str) -> bool {
+    match verbosity.parse::<LevelFilter>() {
+        Ok(level) => {
+            env_logger::Builder::new()
+                .filter_level(LevelFilter::Trace)
+                .init();
+            log::set_max_level(level);
+            true
+        }
+        Err(_) => {
+            env_logger::Builder::new().parse_filters(verbosity).init();
+            false
+        }
+    }
+}
+
+/// Changes the log level, unless the logger was initialized with per-module filters.
+fn set_log_level(level: LevelFilter, reloadable: bool) {
+    if !reloadable {
+        warn!(
+            "The log level `{}` is ignored because the verbosity uses per-module filters.",
+            level
+        );
+        return;
+    }
+    if log::max_level() != level {
+        info!("Setting the log level to `{}`", level);
+        log::set_max_level(level);
+    }
+}
+
+/// The parts of the configuration that are picked up while the controllers are running.
+/// They are replaced whenever the configuration file changes.
+struct ReloadableState {
+    log_level_reloadable: bool,
+    remote_api: Arc<Reloadable<Configuration>>,
+    cats_client: Arc<Reloadable<dyn CatsApi>>,
+    dogs_client: Arc<Reloadable<dyn DogsApi>>,
+    horses_client: Arc<Reloadable<dyn HorsesApi>>,
+    cats_settings: Arc<Reloadable<ControllerSettings>>,
+    dogs_settings: Arc<Reloadable<ControllerSettings>>,
+    horses_settings: Arc<Reloadable<ControllerSettings>>,
+}
+
+impl ReloadableState {
+    fn new(config: &OperatorConfig, log_level_reloadable: bool) -> anyhow::Result<Self> {
+        let remote_api = Arc::new(remote_api_configuration(config)?);
+        Ok(Self {
+            log_level_reloadable,
+            cats_client: Arc::new(Reloadable::new(
+                Arc::new(CatsApiClient::new(remote_api.clone())) as Arc<dyn CatsApi>,
+            )),
+            dogs_client: Arc::new(Reloadable::new(
+                Arc::new(DogsApiClient::new(remote_api.clone())) as Arc<dyn DogsApi>,
+            )),
+            horses_client: Arc::new(Reloadable::new(Arc::new(HorsesApiClient::new(
+                remote_api.clone(),
+            )) as Arc<dyn HorsesApi>)),
+            remote_api: Arc::new(Reloadable::new(remote_api)),
+            cats_settings: Arc::new(Reloadable::new(Arc::new(
+                config.controller_settings(&config.controllers.cats),
+            ))),
+            dogs_settings: Arc::new(Reloadable::new(Arc::new(
+                config.controller_settings(&config.controllers.dogs),
+            ))),
+            horses_settings: Arc::new(Reloadable::new(Arc::new(
+                config.controller_settings(&config.controllers.horses),
+            ))),
+        })
+    }
+
+    /// Applies a changed configuration to the running controllers.
+    fn apply(&self, config: &OperatorConfig) -> anyhow::Result<()> {
+        if let Some(level) = config.log_level() {
+            set_log_level(level, self.log_level_reloadable);
+        }
+
+        let remote_api = Arc::new(remote_api_configuration(config)?);
+        self.cats_client
+            .set(Arc::new(CatsApiClient::new(remote_api.clone())));
+        self.dogs_client
+            .set(Arc::new(DogsApiClient::new(remote_api.clone())));
+        self.horses_client
+            .set(Arc::new(HorsesApiClient::new(remote_api.clone())));
+        self.remote_api.set(remote_api);
+
+        self.cats_settings.set(Arc::new(
+            config.controller_settings(&config.controllers.cats),
+        ));
+        self.dogs_settings.set(Arc::new(
+            config.controller_settings(&config.controllers.dogs),
+        ));
+        self.horses_settings.set(Arc::new(
+            config.controller_settings(&config.controllers.horses),
+        ));
+        Ok(())
+    }
+}
 
+/// Builds the configuration of the remote API clients.
+fn remote_api_configuration(config: &OperatorConfig) -> anyhow::Result<Configuration> {
+    let remote_api = &config.remote_api;
+    Ok(Configuration {
+        base_path: remote_api.url.clone(),
+        client: reqwest::Client::builder()
+            .timeout(Duration::from_secs(remote_api.timeout_seconds))
+            .connect_timeout(Duration::from_secs(remote_api.connect_timeout_seconds))
+            .build()
+            .context("Failed to build the remote API client")?,
+        user_agent: Some(remote_api.user_agent.clone()),
+        bearer_access_token: Some(
+            remote_api
+                .access_token
+                .as_ref()
+                .map(|token| token.0.clone())
+                .unwrap_or_default(),
+        ),
+        ..Default::default()
+    })
+}
+
+/// Turns the result of a health check into a probe response.
+fn health_reply(check: Result<(), String>) -> warp::reply::WithStatus<String> {
+    match check {
+        Ok(()) => warp::reply::with_status("OK".to_string(), warp::http::StatusCode::OK),
+        Err(reason) => {
+            warn!("Health check failed: {}", reason);
+            warp::reply::with_status(reason, warp::http::StatusCode::SERVICE_UNAVAILABLE)
+        }
+    }
+}
+
+/// Waits for the termination signal, either SIGTERM sent by Kubernetes or SIGINT (Ctrl+C).
+async fn wait_for_termination() -> anyhow::Result<()> {
+    let mut sigterm = signal(SignalKind::terminate()).context("Failed to listen for SIGTERM")?;
+
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result.context("Failed to listen for Ctrl+C")?,
+        _ = sigterm.recv() => {}
+    }
+    info!("Termination signal received. Shutting down.");
     Ok(())
 }
+
+/// Waits for the controllers to finish their running reconciliations.
+/// Controllers that are still busy when the grace period is over are aborted.
+async fn drain_controllers(controllers: Vec<JoinHandle<()>>, grace_period: Duration) {
+    info!(
+        "Waiting up to {:?} for running reconciliations to finish...",
+        grace_period
+    );
+    let abort_handles: Vec<_> = controllers
+        .iter()
+        .map(|controller| controller.abort_handle())
+        .collect();
+
+    if timeout(grace_period, futures::future::join_all(controllers))
+        .await
+        .is_err()
+    {
+        warn!(
+            "Reconciliations did not finish within {:?}, aborting the controllers.",
+            grace_period
+        );
+        for abort_handle in abort_handles {
+            abort_handle.abort();
+        }
+    } else {
+        info!("All controllers have stopped.");
+    }
+}
+
+/// Starts a controller for every resource kind in every watched namespace,
+/// and an importer and an orphan sweep for every resource kind when they are enabled.
+/// Returns the handles of the spawned controller tasks, which finish once the shutdown token is cancelled.
+fn start_controllers(
+    kube_client: &KubeClient,
+    state: &ReloadableState,
+    watch_scope: &WatchScope,
+    operator_namespace: &str,
+    import: &ImportConfig,
+    orphan_sweep: &OrphansConfig,
+    shutdown: &CancellationToken,
+) -> Vec<JoinHandle<()>> {
+    let mut controllers = Vec::new();
+
+    // Start the cats controller for the cats.example.com/v1 API group
+    for kube_cats_client in kube_api_clients::<Cat>(kube_client, watch_scope, operator_namespace) {
+        let cats_client = state.cats_client.clone();
+        let settings = state.cats_settings.clone();
+        let shutdown = shutdown.clone();
+        controllers.push(tokio::spawn(async move {
+            let _cats_controller =
+                cats::handle(kube_cats_client, cats_client, settings, shutdown).await;
+        }));
+    }
+    if import.enabled {
+        controllers.push(tokio::spawn(importer::run(
+            kube_api_clients::<Cat>(kube_client, watch_scope, operator_namespace),
+            state.cats_client.clone(),
+            import.namespace.clone(),
+            Duration::from_secs(import.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+    if orphan_sweep.enabled {
+        controllers.push(tokio::spawn(orphans::run(
+            kube_api_clients::<Cat>(kube_client, watch_scope, operator_namespace),
+            state.cats_client.clone(),
+            orphan_sweep.policy,
+            Duration::from_secs(orphan_sweep.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+
+    // Start the dogs controller for the dogs.example.com/v1 API group
+    for kube_dogs_client in kube_api_clients::<Dog>(kube_client, watch_scope, operator_namespace) {
+        let dogs_client = state.dogs_client.clone();
+        let settings = state.dogs_settings.clone();
+        let shutdown = shutdown.clone();
+        controllers.push(tokio::spawn(async move {
+            let _dogs_controller =
+                dogs::handle(kube_dogs_client, dogs_client, settings, shutdown).await;
+        }));
+    }
+    if import.enabled {
+        controllers.push(tokio::spawn(importer::run(
+            kube_api_clients::<Dog>(kube_client, watch_scope, operator_namespace),
+            state.dogs_client.clone(),
+            import.namespace.clone(),
+            Duration::from_secs(import.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+    if orphan_sweep.enabled {
+        controllers.push(tokio::spawn(orphans::run(
+            kube_api_clients::<Dog>(kube_client, watch_scope, operator_namespace),
+            state.dogs_client.clone(),
+            orphan_sweep.policy,
+            Duration::from_secs(orphan_sweep.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+
+    // Start the horses controller for the horses.example.com/v1 API group
+    for kube_horses_client in
+        kube_api_clients::<Horse>(kube_client, watch_scope, operator_namespace)
+    {
+        let horses_client = state.horses_client.clone();
+        let settings = state.horses_settings.clone();
+        let shutdown = shutdown.clone();
+        controllers.push(tokio::spawn(async move {
+            let _horses_controller =
+                horses::handle(kube_horses_client, horses_client, settings, shutdown).await;
+        }));
+    }
+    if import.enabled {
+        controllers.push(tokio::spawn(importer::run(
+            kube_api_clients::<Horse>(kube_client, watch_scope, operator_namespace),
+            state.horses_client.clone(),
+            import.namespace.clone(),
+            Duration::from_secs(import.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+    if orphan_sweep.enabled {
+        controllers.push(tokio::spawn(orphans::run(
+            kube_api_clients::<Horse>(kube_client, watch_scope, operator_namespace),
+            state.horses_client.clone(),
+            orphan_sweep.policy,
+            Duration::from_secs(orphan_sweep.interval_seconds),
+            shutdown.clone(),
+        )));
+    }
+
+    controllers
+}
+
+/// Builds a Kubernetes API client for every namespace in the watch scope,
+/// or a single cluster-wide client when all namespaces are watched.
+/// The clients record the released remote resources in the namespace of the operator.
+fn kube_api_clients<T>(
+    kube_client: &KubeClient,
+    watch_scope: &WatchScope,
+    operator_namespace: &str,
+) -> Vec<Arc<dyn KubeApi<T> + Send + Sync>>
+where
+    T: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + Clone
+        + Send
+        + Sync
+        + 'static
+        + DeserializeOwned
+        + Serialize
+        + Debug
+        + HasStatus,
+    T::Status: Serialize,
+{
+    match watch_scope {
+        WatchScope::AllNamespaces => vec![Arc::new(KubeApiClient {
+            client: Api::all(kube_client.clone()),
+            released_ids: Api::namespaced(kube_client.clone(), operator_namespace),
+        })],
+        WatchScope::Namespaces(namespaces) => namespaces
+            .iter()
+            .map(|namespace| {
+                Arc::new(KubeApiClient {
+                    client: Api::namespaced(kube_client.clone(), namespace),
+                    released_ids: Api::namespaced(kube_client.clone(), operator_namespace),
+                }) as Arc<dyn KubeApi<T> + Send + Sync>
+            })
+            .collect(),
+    }
+}
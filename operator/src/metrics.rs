@@ -0,0 +1,281 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+
+use log::error;
+use prometheus::{
+    histogram_opts, opts, Encoder, HistogramVec, IntCounterVec, IntGaugeVec, Registry, TextEncoder,
+};
+
+use crate::errors::OperatorError;
+
+/// The metrics of the operator, registered once and shared by all controllers.
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Prometheus metrics describing how well the controllers keep up with the remote API.
+pub struct Metrics {
+    registry: Registry,
+    reconciliations: IntCounterVec,
+    reconcile_duration: HistogramVec,
+    errors: IntCounterVec,
+    drift_corrections: IntCounterVec,
+    remote_api_duration: HistogramVec,
+    reconciles_in_flight: IntGaugeVec,
+    queue_depth: IntGaugeVec,
+    /// The objects per kind that wait for their reconciliation to start.
+    queued: Mutex<HashMap<String, HashSet<String>>>,
+    orphans: IntGaugeVec,
+    orphans_handled: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("operator".to_string()), None)
+            .expect("the metrics prefix is valid");
+
+        let reconciliations = IntCounterVec::new(
+            opts!(
+                "reconciliations_total",
+                "Number of reconciliations per kind and result."
+            ),
+            &["kind", "result"],
+        )
+        .expect("the metric definition is valid");
+        let reconcile_duration = HistogramVec::new(
+            histogram_opts!(
+                "reconcile_duration_seconds",
+                "Duration of reconciliations per kind and result."
+            ),
+            &["kind", "result"],
+        )
+        .expect("the metric definition is valid");
+        let errors = IntCounterVec::new(
+            opts!(
+                "errors_total",
+                "Number of failed reconciliations per kind and error."
+            ),
+            &["kind", "error"],
+        )
+        .expect("the metric definition is valid");
+        let drift_corrections = IntCounterVec::new(
+            opts!(
+                "drift_corrections_total",
+                "Number of remote resources that drifted and were updated to match the spec."
+            ),
+            &["kind"],
+        )
+        .expect("the metric definition is valid");
+        let remote_api_duration = HistogramVec::new(
+            histogram_opts!(
+                "remote_api_request_duration_seconds",
+                "Latency of the remote API calls per method and result."
+            ),
+            &["method", "result"],
+        )
+        .expect("the metric definition is valid");
+        let reconciles_in_flight = IntGaugeVec::new(
+            opts!(
+                "reconciles_in_flight",
+                "Number of reconciliations per kind that are running right now. Objects waiting in the controller queue are counted by queue_depth."
+            ),
+            &["kind"],
+        )
+        .expect("the metric definition is valid");
+        let queue_depth = IntGaugeVec::new(
+            opts!(
+                "queue_depth",
+                "Number of objects per kind whose change was picked up by the watch but whose reconciliation has not started yet. Scheduled requeues are not counted."
+            ),
+            &["kind"],
+        )
+        .expect("the metric definition is valid");
+        let orphans = IntGaugeVec::new(
+            opts!(
+                "orphans",
+                "Number of remote resources per kind that no custom resource references, as of the last sweep."
+            ),
+            &["kind"],
+        )
+        .expect("the metric definition is valid");
+        let orphans_handled = IntCounterVec::new(
+            opts!(
+                "orphans_handled_total",
+                "Number of orphaned remote resources per kind that were tagged or deleted."
+            ),
+            &["kind", "action"],
+        )
+        .expect("the metric definition is valid");
+
+        for collector in [
+            Box::new(reconciliations.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(reconcile_duration.clone()),
+            Box::new(errors.clone()),
+            Box::new(drift_corrections.clone()),
+            Box::new(remote_api_duration.clone()),
+            Box::new(reconciles_in_flight.clone()),
+            Box::new(queue_depth.clone()),
+            Box::new(orphans.clone()),
+            Box::new(orphans_handled.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("every metric is registered once");
+        }
+
+        Self {
+            registry,
+            reconciliations,
+            reconcile_duration,
+            errors,
+            drift_corrections,
+            remote_api_duration,
+            reconciles_in_flight,
+            queue_depth,
+            queued: Mutex::new(HashMap::new()),
+            orphans,
+            orphans_handled,
+        }
+    }
+
+    /// Marks an object of the given kind as waiting for its reconciliation.
+    /// An object is counted once however often it is triggered, like the controller queue does.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the object, e.g. `Cat`.
+    /// * `key` - Identifies the object, e.g. `namespace/name`.
+    pub fn reconcile_queued(&self, kind: &str, key: &str) {
+        self.update_queue(kind, |queued| {
+            queued.insert(key.to_string());
+        });
+    }
+
+    /// Marks an object of the given kind as no longer waiting,
+    /// because its reconciliation has started or the object is gone.
+    pub fn reconcile_dequeued(&self, kind: &str, key: &str) {
+        self.update_queue(kind, |queued| {
+            queued.remove(key);
+        });
+    }
+
+    /// Forgets the waiting objects of the given kind, e.g. once its controllers have stopped.
+    pub fn clear_queue(&self, kind: &str) {
+        self.update_queue(kind, HashSet::clear);
+    }
+
+    fn update_queue(&self, kind: &str, update: impl FnOnce(&mut HashSet<String>)) {
+        let mut queued = self.queued.lock().unwrap_or_else(|e| e.into_inner());
+        let queued = queued.entry(kind.to_string()).or_default();
+        update(queued);
+        self.queue_depth
+            .with_label_values(&[kind])
+            .set(i64::try_from(queued.len()).unwrap_or(i64::MAX));
+    }
+
+    /// Marks a reconciliation of the given kind as started.
+    /// The returned guard records its outcome and duration once finished.
+    pub fn reconcile_started(&self, kind: &str) -> ReconcileTimer<'_> {
+        self.reconciles_in_flight.with_label_values(&[kind]).inc();
+        ReconcileTimer {
+            metrics: self,
+            kind: kind.to_string(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Counts a drift that was corrected on the remote side.
+    pub fn drift_corrected(&self, kind: &str) {
+        self.drift_corrections.with_label_values(&[kind]).inc();
+    }
+
+    /// Records the number of orphaned remote resources found by a sweep.
+    pub fn orphans_found(&self, kind: &str, count: usize) {
+        self.orphans
+            .with_label_values(&[kind])
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    /// Counts an orphaned remote resource that was tagged or deleted.
+    pub fn orphan_handled(&self, kind: &str, action: &str) {
+        self.orphans_handled
+            .with_label_values(&[kind, action])
+            .inc();
+    }
+
+    /// Awaits a remote API call and records its latency.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the API method, e.g. `create_cat`.
+    /// * `call` - The pending remote API call.
+    pub async fn observe_remote_call<F, T, E>(&self, method: &str, call: F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let started_at = Instant::now();
+        let result = call.await;
+        self.remote_api_duration
+            .with_label_values(&[method, result_label(result.is_ok())])
+            .observe(started_at.elapsed().as_secs_f64());
+        result
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            error!("Failed to encode metrics: {:?}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Tracks a single reconciliation, see [`Metrics::reconcile_started`].
+pub struct ReconcileTimer<'a> {
+    metrics: &'a Metrics,
+    kind: String,
+    started_at: Instant,
+}
+
+impl ReconcileTimer<'_> {
+    /// Records the outcome of the reconciliation.
+    pub fn finish<T>(self, result: &Result<T, OperatorError>) {
+        let kind = self.kind.as_str();
+        let outcome = result_label(result.is_ok());
+
+        self.metrics
+            .reconciliations
+            .with_label_values(&[kind, outcome])
+            .inc();
+        self.metrics
+            .reconcile_duration
+            .with_label_values(&[kind, outcome])
+            .observe(self.started_at.elapsed().as_secs_f64());
+        if let Err(e) = result {
+            self.metrics
+                .errors
+                .with_label_values(&[kind, e.variant_name()])
+                .inc();
+        }
+    }
+}
+
+impl Drop for ReconcileTimer<'_> {
+    fn drop(&mut self) {
+        self.metrics
+            .reconciles_in_flight
+            .with_label_values(&[self.kind.as_str()])
+            .dec();
+    }
+}
+
+fn result_label(success: bool) -> &'static str {
+    if success {
+        "success"
+    } else {
+        "error"
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use log::{error, info, warn};
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cli::ConfigOverrides,
+    config::OperatorConfig,
+    controllers::reconciler::{RemoteResource, SyncedResource},
+    errors::OperatorError,
+};
+
+/// A value that can be replaced while it is in use, e.g. a remote API client
+/// whose configuration changed. Readers always get the latest value.
+pub struct Reloadable<T: ?Sized> {
+    current: RwLock<Arc<T>>,
+}
+
+impl<T: ?Sized> Reloadable<T> {
+    pub fn new(value: Arc<T>) -> Self {
+        Self {
+            current: RwLock::new(value),
+        }
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> Arc<T> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces the value, calls that are already running keep the previous one.
+    pub fn set(&self, value: Arc<T>) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = value;
+    }
+}
+
+#[async_trait]
+impl<T, R> RemoteResource<T> for Reloadable<R>
+where
+    T: SyncedResource,
+    R: RemoteResource<T> + ?Sized,
+{
+    type Dto = R::Dto;
+
+    fn kube_type_to_dto(resource: &T) -> Self::Dto {
+        R::kube_type_to_dto(resource)
+    }
+
+    fn dto_to_kube_type(dto: Self::Dto) -> T::SyncedSpec {
+        R::dto_to_kube_type(dto)
+    }
+
+    fn dto_uuid(dto: &Self::Dto) -> Option<String> {
+        R::dto_uuid(dto)
+    }
+
+    fn new_resource(name: &str, spec: T::SyncedSpec) -> T {
+        R::new_resource(name, spec)
+    }
+
+    async fn create(&self, dto: Self::Dto) -> Result<Self::Dto, OperatorError> {
+        self.get().create(dto).await
+    }
+
+    async fn get_by_id(&self, uuid: &str) -> Result<Self::Dto, OperatorError> {
+        self.get().get_by_id(uuid).await
+    }
+
+    async fn update_by_id(&self, uuid: &str, dto: Self::Dto) -> Result<Self::Dto, OperatorError> {
+        self.get().update_by_id(uuid, dto).await
+    }
+
+    async fn delete_by_id(&self, uuid: &str) -> Result<(), OperatorError> {
+        self.get().delete_by_id(uuid).await
+    }
+
+    async fn list(&self) -> Result<Vec<Self::Dto>, OperatorError> {
+        self.get().list().await
+    }
+}
+
+/// Periodically reads the configuration file again, together with the overrides,
+/// and hands every valid configuration that differs from the current one to `apply`.
+/// An invalid configuration is logged and ignored, the current one stays in use.
+///
+/// # Arguments
+///
+/// * `overrides` - The command line and environment overrides, including the file path.
+/// * `current` - The configuration currently in use.
+/// * `interval` - The time between two reads.
+/// * `shutdown` - Stops watching once cancelled.
+/// * `apply` - Applies a changed configuration.
+pub async fn watch_config<F>(
+    overrides: &ConfigOverrides,
+    mut current: OperatorConfig,
+    interval: Duration,
+    shutdown: CancellationToken,
+    apply: F,
+) where
+    F: Fn(&OperatorConfig),
+{
+    info!(
+        "Watching the configuration file {:?} for changes",
+        overrides.config_file
+    );
+    loop {
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = shutdown.cancelled() => return,
+        }
+
+        match overrides.load() {
+            Ok(config) if config != current => {
+                info!("The configuration has changed, applying it");
+                let restart_required = config.restart_required_changes(&current);
+                if !restart_required.is_empty() {
+                    warn!(
+                        "Changes to {} only take effect after a restart",
+                        restart_required.join(", ")
+                    );
+                }
+                apply(&config);
+                current = config;
+            }
+            Ok(_) => {}
+            Err(e) => error!("Ignoring the changed configuration: {}", e),
+        }
+    }
+}
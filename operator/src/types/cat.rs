@@ -1,10 +1,11 @@
-use chrono::Utc;
 // This file is generated by kopgen. Do not edit manually. If you need to make adjustments add it to .openapi-generator-ignore file.
-use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Condition;
 use kube::CustomResource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::status::{conditions_schema, DriftReport, ResourceStatus, SyncStatus};
+
 #[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, PartialEq, CustomResource)]
 #[kube(
     group = "example.com",
@@ -14,7 +15,9 @@ use serde::{Deserialize, Serialize};
     derive = "PartialEq",
     status = "CatStatus",
     namespaced,
-    printcolumn = r#"{"name": "Status", "type": "string", "jsonPath": ".status.conditions[0].status", "description": "The current status of the resource"}"#,
+    printcolumn = r#"{"name": "Ready", "type": "string", "jsonPath": ".status.conditions[?(@.type==\"Ready\")].status", "description": "Whether the remote resource is synced and healthy"}"#,
+    printcolumn = r#"{"name": "Reason", "type": "string", "jsonPath": ".status.conditions[?(@.type==\"Ready\")].reason", "priority": 1, "description": "Why the resource is or is not ready"}"#,
+    printcolumn = r#"{"name": "Suspended", "type": "string", "jsonPath": ".status.conditions[?(@.type==\"Suspended\")].status", "description": "Whether the reconciliation of the resource is suspended"}"#,
     printcolumn = r#"{"name": "Reference ID", "type": "string", "jsonPath": ".status.uuid", "description": "The reference ID of the resource"}"#,
     printcolumn = r#"{"name": "Age", "type": "date", "jsonPath": ".metadata.creationTimestamp", "description": "The creation time of the resource"}"#
 )]
@@ -24,65 +27,73 @@ pub struct CatSpec {
     pub age: i32,
 }
 
+/// The observed state of a Cat and of its remote counterpart.
 #[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
 pub struct CatStatus {
     pub uuid: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    #[schemars(schema_with = "conditions")]
+    #[schemars(schema_with = "conditions_schema")]
     pub conditions: Vec<Condition>,
     #[serde(rename = "observedGeneration")]
     pub observed_generation: Option<i64>,
+    #[serde(
+        rename = "deletionPolicy",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deletion_policy: Option<String>,
+    #[serde(rename = "lastDrift", default, skip_serializing_if = "Option::is_none")]
+    pub last_drift: Option<DriftReport>,
+    #[serde(flatten)]
+    pub sync: SyncStatus,
 }
 
-fn conditions(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
-    serde_json::from_value(serde_json::json!({
-        "type": "array",
-        "x-kubernetes-list-type": "map",
-        "x-kubernetes-list-map-keys": ["type"],
-        "items": {
-            "type": "object",
-            "properties": {
-                "lastTransitionTime": { "format": "date-time", "type": "string" },
-                "message": { "type": "string" },
-                "observedGeneration": { "type": "integer", "format": "int64", "default": 0 },
-                "reason": { "type": "string" },
-                "status": { "type": "string" },
-                "type": { "type": "string" }
-            },
-            "required": [
-                "lastTransitionTime",
-                "message",
-                "reason",
-                "status",
-                "type"
-            ],
-        },
-    }))
-    .unwrap()
-}
+impl ResourceStatus for CatStatus {
+    fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    fn set_uuid(&mut self, uuid: String) {
+        self.uuid = Some(uuid);
+    }
+
+    fn observed_generation(&self) -> Option<i64> {
+        self.observed_generation
+    }
+
+    fn set_observed_generation(&mut self, generation: Option<i64>) {
+        self.observed_generation = generation;
+    }
+
+    fn deletion_policy(&self) -> Option<&str> {
+        self.deletion_policy.as_deref()
+    }
 
-impl CatStatus {
-    /// Sets or updates a condition within the Cat's status.
-    /// Ensures that each condition type is unique.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The `Condition` to add or update.
-    pub fn set_condition(&mut self, mut condition: Condition) {
-        // Set the `last_transition_time` to the current time.
-        condition.last_transition_time = Time(Utc::now());
+    fn set_deletion_policy(&mut self, policy: String) {
+        self.deletion_policy = Some(policy);
+    }
+
+    fn sync(&self) -> &SyncStatus {
+        &self.sync
+    }
+
+    fn sync_mut(&mut self) -> &mut SyncStatus {
+        &mut self.sync
+    }
+
+    fn last_drift(&self) -> Option<&DriftReport> {
+        self.last_drift.as_ref()
+    }
+
+    fn set_last_drift(&mut self, report: DriftReport) {
+        self.last_drift = Some(report);
+    }
+
+    fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
 
-        // Check if a condition with the same type already exists.
-        if let Some(existing) = self
-            .conditions
-            .iter_mut()
-            .find(|c| c.type_ == condition.type_)
-        {
-            // Update the existing condition to prevent duplication.
-            *existing = condition;
-        } else {
-            // Add the new condition if it doesn't exist.
-            self.conditions.push(condition);
-        }
+    fn conditions_mut(&mut self) -> &mut Vec<Condition> {
+        &mut self.conditions
     }
 }
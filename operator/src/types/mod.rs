@@ -1,3 +1,4 @@
 pub mod cat;
 pub mod dog;
 pub mod horse;
+pub mod status;
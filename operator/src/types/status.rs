@@ -0,0 +1,260 @@
+use std::{collections::BTreeSet, fmt::Debug};
+
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::OperatorError;
+
+/// When the remote resource was last synchronized, and why the attempts since then failed.
+/// Flattened into the status of every synchronized resource.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct SyncStatus {
+    /// When the remote resource was last synchronized successfully.
+    #[serde(rename = "lastSyncTime", default)]
+    #[schemars(schema_with = "date_time_schema")]
+    pub last_sync_time: Option<Time>,
+    /// When the last synchronization was attempted, successfully or not.
+    #[serde(rename = "lastAttemptTime", default)]
+    #[schemars(schema_with = "date_time_schema")]
+    pub last_attempt_time: Option<Time>,
+    /// The error of the last failed attempt, cleared once an attempt succeeds.
+    #[serde(rename = "lastErrorMessage", default)]
+    pub last_error_message: Option<String>,
+    /// The HTTP status code of the response that caused the last error, if there was a response.
+    #[serde(rename = "lastErrorCode", default)]
+    pub last_error_code: Option<u16>,
+    /// The number of attempts that failed since the last successful one.
+    #[serde(rename = "consecutiveFailures", default)]
+    pub consecutive_failures: u32,
+}
+
+impl SyncStatus {
+    /// Records a successful attempt, clearing the error of previous attempts.
+    pub fn succeeded(&mut self, at: Time) {
+        self.last_sync_time = Some(at.clone());
+        self.last_attempt_time = Some(at);
+        self.last_error_message = None;
+        self.last_error_code = None;
+        self.consecutive_failures = 0;
+    }
+
+    /// Records a failed attempt.
+    pub fn failed(&mut self, at: Time, error: &OperatorError) {
+        self.last_attempt_time = Some(at);
+        self.last_error_message = Some(error.to_string());
+        self.last_error_code = error.status_code();
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+}
+
+/// A field of the spec whose remote value differs from the desired one.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct FieldDrift {
+    /// The path of the field in the spec, e.g. `age`.
+    pub field: String,
+    /// The value of the field in the spec, as JSON.
+    pub desired: String,
+    /// The value of the field in the remote resource, as JSON.
+    pub remote: String,
+}
+
+/// The last drift of the remote resource from the spec, kept so it can be audited
+/// after it has been handled.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct DriftReport {
+    /// When the drift was detected.
+    #[serde(rename = "detectedAt", default)]
+    #[schemars(schema_with = "date_time_schema")]
+    pub detected_at: Option<Time>,
+    /// The drift policy the drift was handled with.
+    #[serde(default)]
+    pub policy: String,
+    /// Whether the remote resource was updated to match the spec again.
+    #[serde(default)]
+    pub corrected: bool,
+    /// The fields that differed.
+    #[serde(default)]
+    pub fields: Vec<FieldDrift>,
+}
+
+impl DriftReport {
+    /// Compares the desired spec with the spec read from the remote resource, field by field.
+    /// Nested objects are compared by their fields, any other value as a whole.
+    pub fn between<S: Serialize>(desired: &S, remote: &S, policy: &str, detected_at: Time) -> Self {
+        let desired = serde_json::to_value(desired).unwrap_or_default();
+        let remote = serde_json::to_value(remote).unwrap_or_default();
+        let mut fields = Vec::new();
+        diff_values("", &desired, &remote, &mut fields);
+        DriftReport {
+            detected_at: Some(detected_at),
+            policy: policy.to_string(),
+            corrected: false,
+            fields,
+        }
+    }
+
+    /// Checks whether the same fields drifted to the same values as in the other report.
+    pub fn same_drift(&self, other: &DriftReport) -> bool {
+        self.fields == other.fields
+    }
+
+    /// Describes the fields that differed, for logs and events.
+    pub fn summary(&self) -> String {
+        self.fields
+            .iter()
+            .map(|drift| {
+                format!(
+                    "{} (desired {}, remote {})",
+                    drift.field, drift.desired, drift.remote
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn diff_values(path: &str, desired: &Value, remote: &Value, fields: &mut Vec<FieldDrift>) {
+    if desired == remote {
+        return;
+    }
+    if let (Value::Object(desired), Value::Object(remote)) = (desired, remote) {
+        let keys = desired.keys().chain(remote.keys()).collect::<BTreeSet<_>>();
+        for key in keys {
+            let path = if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            diff_values(
+                &path,
+                desired.get(key).unwrap_or(&Value::Null),
+                remote.get(key).unwrap_or(&Value::Null),
+                fields,
+            );
+        }
+        return;
+    }
+    fields.push(FieldDrift {
+        field: path.to_string(),
+        desired: desired.to_string(),
+        remote: remote.to_string(),
+    });
+}
+
+/// The fields every synchronized resource keeps in its status,
+/// and the bookkeeping of its conditions and observed generation.
+pub trait ResourceStatus: Default + Clone + Debug + Send + Sync {
+    /// Returns the ID of the remote counterpart, if one has been created.
+    fn uuid(&self) -> Option<&str>;
+
+    /// Records the ID of the remote counterpart.
+    fn set_uuid(&mut self, uuid: String);
+
+    /// Returns the generation of the resource that was last synchronized.
+    fn observed_generation(&self) -> Option<i64>;
+
+    /// Records the generation of the resource that was last synchronized.
+    fn set_observed_generation(&mut self, generation: Option<i64>);
+
+    /// Returns the deletion policy the resource was last reconciled with.
+    fn deletion_policy(&self) -> Option<&str>;
+
+    /// Records the deletion policy of the resource.
+    fn set_deletion_policy(&mut self, policy: String);
+
+    /// Returns when the remote resource was last synchronized.
+    fn sync(&self) -> &SyncStatus;
+
+    /// Returns when the remote resource was last synchronized, for modification.
+    fn sync_mut(&mut self) -> &mut SyncStatus;
+
+    /// Returns the last drift of the remote resource, if it ever drifted.
+    fn last_drift(&self) -> Option<&DriftReport>;
+
+    /// Records the last drift of the remote resource.
+    fn set_last_drift(&mut self, report: DriftReport);
+
+    /// Returns the conditions of the resource.
+    fn conditions(&self) -> &[Condition];
+
+    /// Returns the conditions of the resource for modification.
+    fn conditions_mut(&mut self) -> &mut Vec<Condition>;
+
+    /// Checks whether the given generation of the resource has been synchronized.
+    fn is_observed(&self, generation: Option<i64>) -> bool {
+        self.observed_generation() == generation
+    }
+
+    /// Returns the condition of the given type, if there is one.
+    fn condition(&self, type_: &str) -> Option<&Condition> {
+        self.conditions()
+            .iter()
+            .find(|condition| condition.type_ == type_)
+    }
+
+    /// Sets or updates a condition, keeping condition types unique.
+    /// The `lastTransitionTime` of an existing condition is kept unless its status changes.
+    fn set_condition(&mut self, mut condition: Condition) {
+        let conditions = self.conditions_mut();
+        match conditions
+            .iter_mut()
+            .find(|existing| existing.type_ == condition.type_)
+        {
+            Some(existing) => {
+                if existing.status == condition.status {
+                    condition.last_transition_time = existing.last_transition_time.clone();
+                }
+                *existing = condition;
+            }
+            None => conditions.push(condition),
+        }
+    }
+
+    /// Removes the condition of the given type, returning it if there was one.
+    fn remove_condition(&mut self, type_: &str) -> Option<Condition> {
+        let conditions = self.conditions_mut();
+        let index = conditions
+            .iter()
+            .position(|condition| condition.type_ == type_)?;
+        Some(conditions.remove(index))
+    }
+}
+
+/// The schema of the conditions of every status, a list keyed by the condition type.
+pub fn conditions_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    serde_json::from_value(serde_json::json!({
+        "type": "array",
+        "x-kubernetes-list-type": "map",
+        "x-kubernetes-list-map-keys": ["type"],
+        "items": {
+            "type": "object",
+            "properties": {
+                "lastTransitionTime": { "format": "date-time", "type": "string" },
+                "message": { "type": "string" },
+                "observedGeneration": { "type": "integer", "format": "int64", "default": 0 },
+                "reason": { "type": "string" },
+                "status": { "type": "string" },
+                "type": { "type": "string" }
+            },
+            "required": [
+                "lastTransitionTime",
+                "message",
+                "reason",
+                "status",
+                "type"
+            ],
+        },
+    }))
+    .unwrap()
+}
+
+fn date_time_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    serde_json::from_value(serde_json::json!({
+        "type": "string",
+        "format": "date-time",
+        "nullable": true,
+    }))
+    .unwrap()
+}
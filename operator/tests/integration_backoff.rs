@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use operator::controllers::backoff::Backoff;
+    use std::time::Duration;
+
+    fn setup_backoff() -> Backoff {
+        Backoff::new(Duration::from_secs(5), Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_delay_grows_exponentially_with_jitter() {
+        let backoff = setup_backoff();
+
+        for expected in [5, 10, 20, 40] {
+            let delay = backoff.next_delay("default/cat");
+
+            assert!(delay >= Duration::from_secs(expected) / 2);
+            assert!(delay <= Duration::from_secs(expected));
+        }
+        assert_eq!(backoff.failures("default/cat"), 4);
+    }
+
+    #[test]
+    fn test_delay_is_capped() {
+        let backoff = setup_backoff();
+
+        for _ in 0..100 {
+            assert!(backoff.next_delay("default/cat") <= Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn test_reset_forgets_failures() {
+        let backoff = setup_backoff();
+        for _ in 0..5 {
+            backoff.next_delay("default/cat");
+        }
+
+        backoff.reset("default/cat");
+
+        assert_eq!(backoff.failures("default/cat"), 0);
+        assert!(backoff.next_delay("default/cat") <= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_failures_are_tracked_per_resource() {
+        let backoff = setup_backoff();
+        for _ in 0..5 {
+            backoff.next_delay("default/broken-cat");
+        }
+
+        assert_eq!(backoff.failures("default/healthy-cat"), 0);
+        assert!(backoff.next_delay("default/healthy-cat") <= Duration::from_secs(5));
+    }
+}
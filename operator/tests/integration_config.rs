@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use operator::{
+        cli::ConfigOverrides,
+        config::{ControllerSettings, OperatorConfig, OrphanPolicy},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let config = OperatorConfig::default();
+
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.controller_settings(&config.controllers.cats),
+            ControllerSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_config_is_read_from_yaml_with_defaults_for_missing_fields() {
+        let config = OperatorConfig::from_yaml(
+            r#"
+requeue:
+  intervalSeconds: 60
+controllers:
+  dogs:
+    concurrency: 2
+server:
+  bindAddress: 127.0.0.1:9000
+"#,
+        )
+        .unwrap();
+
+        let settings = config.controller_settings(&config.controllers.dogs);
+        assert_eq!(settings.requeue_after, Duration::from_secs(60));
+        assert_eq!(settings.backoff_base, Duration::from_secs(5));
+        assert_eq!(settings.concurrency, 2);
+        assert_eq!(config.controllers.cats.concurrency, 0);
+        assert_eq!(config.server.bind_address.to_string(), "127.0.0.1:9000");
+        assert_eq!(config.watch.namespaces, vec!["default".to_string()]);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let result = OperatorConfig::from_yaml("requeue:\n  intervalSecs: 60\n");
+
+        assert!(result.unwrap_err().to_string().contains("intervalSecs"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut config = OperatorConfig::default();
+        config.requeue.interval_seconds = 0;
+        config.watch.namespaces = vec!["Not_A_Namespace".to_string()];
+        config.remote_api.url = "not a url".to_string();
+
+        let error = config.validate().unwrap_err().to_string();
+
+        assert!(error.contains("requeue.intervalSeconds"));
+        assert!(error.contains("Not_A_Namespace"));
+        assert!(error.contains("remoteApi.url"));
+    }
+
+    #[test]
+    fn test_namespaces_are_not_validated_when_watching_all_namespaces() {
+        let mut config = OperatorConfig::default();
+        config.watch.all_namespaces = true;
+        config.watch.namespaces = vec![];
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_the_file() {
+        let mut config = OperatorConfig::from_yaml(
+            r#"
+requeue:
+  intervalSeconds: 60
+watch:
+  namespaces: [default]
+remoteApi:
+  url: http://fake-server:8080
+"#,
+        )
+        .unwrap();
+        let overrides = ConfigOverrides {
+            namespaces: vec!["pets".to_string()],
+            requeue_interval: Some(10),
+            horses_concurrency: Some(8),
+            api_url: Some("http://localhost:8080".to_string()),
+            ..Default::default()
+        };
+
+        overrides.apply(&mut config);
+
+        assert_eq!(config.watch.namespaces, vec!["pets".to_string()]);
+        assert_eq!(config.requeue.interval_seconds, 10);
+        assert_eq!(config.controllers.horses.concurrency, 8);
+        assert_eq!(config.remote_api.url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_overrides_that_were_not_given_keep_the_file_values() {
+        let mut config = OperatorConfig::from_yaml("requeue:\n  intervalSeconds: 60\n").unwrap();
+
+        ConfigOverrides::default().apply(&mut config);
+
+        assert_eq!(config.requeue.interval_seconds, 60);
+    }
+
+    #[test]
+    fn test_invalid_log_level_is_rejected() {
+        let config = OperatorConfig::from_yaml("logLevel: loud\n").unwrap();
+
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("logLevel"));
+        assert_eq!(
+            OperatorConfig::from_yaml("logLevel: debug\n")
+                .unwrap()
+                .log_level(),
+            Some(log::LevelFilter::Debug)
+        );
+    }
+
+    #[test]
+    fn test_access_token_is_read_from_the_file() {
+        let path = std::env::temp_dir().join(format!("operator-token-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "secret-token\n").unwrap();
+        let mut config = OperatorConfig::default();
+        config.remote_api.access_token_file = Some(path.clone());
+
+        config.read_access_token().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let token = config.remote_api.access_token.unwrap();
+        assert_eq!(token.0, "secret-token");
+        assert_eq!(format!("{:?}", token), "AccessToken(***)");
+    }
+
+    #[test]
+    fn test_restart_required_changes_are_listed() {
+        let config = OperatorConfig::default();
+        let mut changed = config.clone();
+        changed.requeue.interval_seconds = 60;
+        changed.remote_api.url = "http://fake-server:8080".to_string();
+        assert!(changed.restart_required_changes(&config).is_empty());
+
+        changed.watch.namespaces = vec!["pets".to_string()];
+        changed.controllers.cats.concurrency = 2;
+        assert_eq!(
+            changed.restart_required_changes(&config),
+            vec!["controllers", "watch"]
+        );
+    }
+
+    #[test]
+    fn test_import_namespace_must_be_watched() {
+        let mut config = OperatorConfig::from_yaml(
+            r#"
+watch:
+  namespaces: [default]
+import:
+  enabled: true
+  namespace: pets
+"#,
+        )
+        .unwrap();
+
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("import.namespace"));
+
+        config.watch.namespaces.push("pets".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_deleting_orphans_requires_watching_all_namespaces() {
+        let mut config = OperatorConfig::from_yaml(
+            r#"
+orphans:
+  enabled: true
+  policy: delete
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.orphans.policy, OrphanPolicy::Delete);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("orphans.policy"));
+
+        config.watch.all_namespaces = true;
+        assert!(config.validate().is_ok());
+    }
+}
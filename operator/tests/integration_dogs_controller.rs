@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use kube::{
+        runtime::events::{Event, EventType},
+        Api,
+    };
+    use mockall::mock;
+    use openapi::apis::dogs_api::{
+        CreateDogError, DeleteDogByIdError, GetDogByIdError, GetDogsError, UpdateDogByIdError,
+    };
+    use openapi::apis::ResponseContent;
+    use openapi::{apis::dogs_api::DogsApi, apis::Error, models::Dog as DogDto};
+    use operator::{
+        controllers::{
+            dogs::{converters, ContextData},
+            reconciler::{reconcile, RemoteResource},
+        },
+        errors::{ErrorClass, OperatorError},
+        types::dog::{Dog, DogSpec, DogStatus},
+        KubeApi,
+    };
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    mock! {
+        pub DogsApi {}
+
+        #[async_trait]
+        impl DogsApi for DogsApi {
+            async fn create_dog<'dog>(&self, dog: DogDto) -> Result<DogDto, Error<CreateDogError>>;
+            async fn delete_dog_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteDogByIdError>>;
+            async fn get_dog_by_id<'id>(&self, id: &'id str) -> Result<DogDto, Error<GetDogByIdError>>;
+            async fn update_dog_by_id<'id, 'dog>(&self, id: &'id str, dog: DogDto) -> Result<DogDto, Error<UpdateDogByIdError>>;
+            async fn get_dogs(&self) -> Result<Vec<DogDto>, Error<GetDogsError>>;
+        }
+    }
+
+    mock! {
+        pub KubeApiClient {}
+
+        #[async_trait]
+        impl KubeApi<Dog> for KubeApiClient {
+            async fn add_finalizer(&self, resource: &mut Dog) -> Result<(), OperatorError>;
+            async fn remove_finalizer(&self, resource: &mut Dog) -> Result<(), OperatorError>;
+            async fn update_status(&self, resource: &mut Dog) -> Result<(), OperatorError>;
+            async fn list(&self) -> Result<Vec<Dog>, OperatorError>;
+            async fn create(&self, resource: &Dog) -> Result<Dog, OperatorError>;
+            async fn patch(&self, resource: &Dog, patch_params: &kube::api::PatchParams, patch: &kube::api::Patch<serde_json::Value>) -> Result<Dog, OperatorError>;
+            async fn publish_event(&self, resource: &Dog, event: Event) -> Result<(), OperatorError>;
+            async fn released_ids(&self) -> Result<std::collections::HashSet<String>, OperatorError>;
+            async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+            fn get_client(&self) -> Api<Dog>;
+            fn set_client(&mut self, client: Api<Dog>);
+        }
+    }
+
+    fn setup_dog() -> Dog {
+        Dog {
+            metadata: kube::api::ObjectMeta {
+                name: Some("fido".to_string()),
+                ..Default::default()
+            },
+            spec: DogSpec {
+                name: "Fido".to_string(),
+                breed: "Bulldog".to_string(),
+                age: 3,
+            },
+            status: None,
+        }
+    }
+
+    fn setup_dog_dto(uuid: Uuid) -> DogDto {
+        DogDto {
+            uuid: Some(uuid),
+            name: "Fido".to_string(),
+            breed: "Bulldog".to_string(),
+            age: 3,
+        }
+    }
+
+    #[test]
+    fn test_kube_type_to_dto_copies_the_spec_and_the_uuid() {
+        let uuid = Uuid::new_v4();
+        let mut dog = setup_dog();
+        dog.status = Some(DogStatus {
+            uuid: Some(uuid.to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(converters::kube_type_to_dto(dog), setup_dog_dto(uuid));
+    }
+
+    #[test]
+    fn test_kube_type_to_dto_without_status_has_no_uuid() {
+        let dto = converters::kube_type_to_dto(setup_dog());
+
+        assert_eq!(dto.uuid, None);
+    }
+
+    #[test]
+    fn test_dto_to_kube_type_copies_the_spec() {
+        let spec = converters::dto_to_kube_type(setup_dog_dto(Uuid::new_v4()));
+
+        assert_eq!(spec, setup_dog().spec);
+    }
+
+    #[tokio::test]
+    async fn test_remote_calls_use_the_dogs_endpoints() {
+        let mut mock_dogs_api = MockDogsApi::new();
+        let uuid = Uuid::new_v4();
+        let id = uuid.to_string();
+
+        mock_dogs_api
+            .expect_create_dog()
+            .times(1)
+            .returning(move |dto| {
+                Ok(DogDto {
+                    uuid: Some(uuid),
+                    ..dto
+                })
+            });
+        mock_dogs_api
+            .expect_get_dog_by_id()
+            .withf(move |id| id == uuid.to_string())
+            .times(1)
+            .returning(move |_| Ok(setup_dog_dto(uuid)));
+        mock_dogs_api
+            .expect_update_dog_by_id()
+            .withf(move |id, _| id == uuid.to_string())
+            .times(1)
+            .returning(|_, dto| Ok(dto));
+        mock_dogs_api
+            .expect_delete_dog_by_id()
+            .withf(move |id| id == uuid.to_string())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_dogs_api
+            .expect_get_dogs()
+            .times(1)
+            .returning(move || Ok(vec![setup_dog_dto(uuid)]));
+
+        let dogs_api = Arc::new(mock_dogs_api) as Arc<dyn DogsApi>;
+        let dto = converters::kube_type_to_dto(setup_dog());
+
+        let created = dogs_api.create(dto.clone()).await.unwrap();
+        assert_eq!(created.uuid, Some(uuid));
+        assert_eq!(dogs_api.get_by_id(&id).await.unwrap(), setup_dog_dto(uuid));
+        assert!(dogs_api.update_by_id(&id, dto).await.is_ok());
+        assert!(dogs_api.delete_by_id(&id).await.is_ok());
+        assert_eq!(dogs_api.list().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remote_errors_keep_their_status_code() {
+        let mut mock_dogs_api = MockDogsApi::new();
+        mock_dogs_api
+            .expect_get_dog_by_id()
+            .times(1)
+            .returning(|_| {
+                Err(Error::ResponseError(ResponseContent {
+                    status: reqwest::StatusCode::NOT_FOUND,
+                    content: "Not Found".to_string(),
+                    entity: None,
+                }))
+            });
+
+        let dogs_api = Arc::new(mock_dogs_api) as Arc<dyn DogsApi>;
+        let error = dogs_api
+            .get_by_id(&Uuid::new_v4().to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OperatorError::FailedToGetResource(_)));
+        assert_eq!(error.class(), ErrorClass::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_creates_a_remote_dog() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_dogs_api = MockDogsApi::new();
+        let dog = Arc::new(setup_dog());
+        let uuid = Uuid::new_v4();
+
+        mock_dogs_api
+            .expect_create_dog()
+            .withf(|dto| dto == &converters::kube_type_to_dto(setup_dog()))
+            .times(1)
+            .returning(move |dto| {
+                Ok(DogDto {
+                    uuid: Some(uuid),
+                    ..dto
+                })
+            });
+        mock_dogs_api
+            .expect_get_dog_by_id()
+            .times(1)
+            .returning(move |_| Ok(setup_dog_dto(uuid)));
+        kube_client
+            .expect_add_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .withf(move |dog| dog.status.as_ref().unwrap().uuid == Some(uuid.to_string()))
+            .times(2)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Created")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Dog>>;
+        let dogs_api = Arc::new(mock_dogs_api) as Arc<dyn DogsApi>;
+
+        let result = reconcile(
+            Arc::clone(&dog),
+            Arc::new(ContextData::new(kube_client.clone(), dogs_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use kube::core::ErrorResponse;
+    use openapi::apis::{cats_api::CreateCatError, Error, ResponseContent};
+    use operator::errors::{ErrorClass, OperatorError, RemoteApiError};
+
+    fn remote_error(status: reqwest::StatusCode) -> OperatorError {
+        let error: Error<CreateCatError> = Error::ResponseError(ResponseContent {
+            status,
+            content: String::new(),
+            entity: None,
+        });
+        OperatorError::FailedToCreateResource(RemoteApiError::from(error).into())
+    }
+
+    fn kube_error(code: u16) -> OperatorError {
+        let error = kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: String::new(),
+            reason: String::new(),
+            code,
+        });
+        OperatorError::FailedToPatchResource(error.into())
+    }
+
+    #[test]
+    fn test_status_codes_are_classified() {
+        assert_eq!(ErrorClass::from_status(400), ErrorClass::Permanent);
+        assert_eq!(ErrorClass::from_status(422), ErrorClass::Permanent);
+        assert_eq!(ErrorClass::from_status(401), ErrorClass::Unauthorized);
+        assert_eq!(ErrorClass::from_status(403), ErrorClass::Unauthorized);
+        assert_eq!(ErrorClass::from_status(404), ErrorClass::NotFound);
+        assert_eq!(ErrorClass::from_status(409), ErrorClass::Conflict);
+        assert_eq!(ErrorClass::from_status(429), ErrorClass::Retryable);
+        assert_eq!(ErrorClass::from_status(500), ErrorClass::Retryable);
+        assert_eq!(ErrorClass::from_status(503), ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn test_remote_api_errors_are_classified_by_status_code() {
+        assert_eq!(
+            remote_error(reqwest::StatusCode::BAD_REQUEST).class(),
+            ErrorClass::Permanent
+        );
+        assert_eq!(
+            remote_error(reqwest::StatusCode::UNAUTHORIZED).class(),
+            ErrorClass::Unauthorized
+        );
+        assert_eq!(
+            remote_error(reqwest::StatusCode::BAD_GATEWAY).class(),
+            ErrorClass::Retryable
+        );
+    }
+
+    #[test]
+    fn test_remote_api_errors_without_response_are_retryable() {
+        let error: Error<CreateCatError> =
+            Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        let error = OperatorError::FailedToCreateResource(RemoteApiError::from(error).into());
+
+        assert_eq!(error.class(), ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn test_kube_api_errors_are_classified_by_status_code() {
+        assert_eq!(kube_error(409).class(), ErrorClass::Conflict);
+        assert_eq!(kube_error(404).class(), ErrorClass::NotFound);
+        assert_eq!(kube_error(500).class(), ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn test_invalid_resources_are_permanent() {
+        let error = OperatorError::InvalidResource("uuid is empty".to_string());
+
+        assert_eq!(error.class(), ErrorClass::Permanent);
+    }
+
+    #[test]
+    fn test_status_code_of_errors_with_a_response() {
+        assert_eq!(
+            remote_error(reqwest::StatusCode::BAD_GATEWAY).status_code(),
+            Some(502)
+        );
+        assert_eq!(kube_error(409).status_code(), Some(409));
+        assert_eq!(
+            OperatorError::InvalidResource("uuid is empty".to_string()).status_code(),
+            None
+        );
+    }
+}
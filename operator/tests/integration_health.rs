@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use operator::health::{ControllerState, Health};
+
+    const CATS_CONTROLLER: &str = "/apis/example.com/v1/namespaces/default/cats";
+
+    fn setup_health() -> Health {
+        let health = Health::default();
+        health.set_crds_established(true);
+        health.set_controller_state(CATS_CONTROLLER, ControllerState::Watching);
+        health
+    }
+
+    #[test]
+    fn test_ready_when_crds_established_and_watches_live() {
+        let health = setup_health();
+
+        assert!(health.liveness().is_ok());
+        assert!(health.readiness().is_ok());
+    }
+
+    #[test]
+    fn test_not_ready_until_crds_established() {
+        let health = setup_health();
+        health.set_crds_established(false);
+
+        assert!(health.liveness().is_ok());
+        assert!(health.readiness().is_err());
+    }
+
+    #[test]
+    fn test_not_ready_while_watch_is_failing() {
+        let health = setup_health();
+        health.set_controller_state(CATS_CONTROLLER, ControllerState::WatchFailing);
+
+        assert!(health.liveness().is_ok());
+        assert!(health.readiness().is_err());
+    }
+
+    #[test]
+    fn test_not_alive_when_controller_stopped() {
+        let health = setup_health();
+        health.set_controller_state(CATS_CONTROLLER, ControllerState::Stopped);
+        health.set_controller_state(CATS_CONTROLLER, ControllerState::Watching);
+
+        assert!(health.liveness().is_err());
+        assert!(health.readiness().is_err());
+    }
+
+    #[test]
+    fn test_alive_again_after_controllers_cleared() {
+        let health = setup_health();
+        health.set_controller_state(CATS_CONTROLLER, ControllerState::Stopped);
+        health.clear_controllers();
+
+        assert!(health.liveness().is_ok());
+    }
+
+    #[test]
+    fn test_remote_api_only_affects_readiness_when_enabled() {
+        let health = setup_health();
+        health.set_remote_api_reachable(false);
+
+        assert!(health.readiness().is_ok());
+
+        health.enable_remote_api_check();
+
+        assert!(health.readiness().is_err());
+
+        health.set_remote_api_reachable(true);
+
+        assert!(health.readiness().is_ok());
+    }
+}
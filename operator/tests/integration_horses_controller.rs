@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use kube::{
+        runtime::events::{Event, EventType},
+        Api,
+    };
+    use mockall::mock;
+    use openapi::apis::horses_api::{
+        CreateHorseError, DeleteHorseByIdError, GetHorseByIdError, GetHorsesError,
+        UpdateHorseByIdError,
+    };
+    use openapi::apis::ResponseContent;
+    use openapi::{apis::horses_api::HorsesApi, apis::Error, models::Horse as HorseDto};
+    use operator::{
+        controllers::{
+            horses::{converters, ContextData},
+            reconciler::{reconcile, RemoteResource},
+        },
+        errors::{ErrorClass, OperatorError},
+        types::horse::{Horse, HorseSpec, HorseStatus},
+        KubeApi,
+    };
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    mock! {
+        pub HorsesApi {}
+
+        #[async_trait]
+        impl HorsesApi for HorsesApi {
+            async fn create_horse<'horse>(&self, horse: HorseDto) -> Result<HorseDto, Error<CreateHorseError>>;
+            async fn delete_horse_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteHorseByIdError>>;
+            async fn get_horse_by_id<'id>(&self, id: &'id str) -> Result<HorseDto, Error<GetHorseByIdError>>;
+            async fn update_horse_by_id<'id, 'horse>(&self, id: &'id str, horse: HorseDto) -> Result<HorseDto, Error<UpdateHorseByIdError>>;
+            async fn get_horses(&self) -> Result<Vec<HorseDto>, Error<GetHorsesError>>;
+        }
+    }
+
+    mock! {
+        pub KubeApiClient {}
+
+        #[async_trait]
+        impl KubeApi<Horse> for KubeApiClient {
+            async fn add_finalizer(&self, resource: &mut Horse) -> Result<(), OperatorError>;
+            async fn remove_finalizer(&self, resource: &mut Horse) -> Result<(), OperatorError>;
+            async fn update_status(&self, resource: &mut Horse) -> Result<(), OperatorError>;
+            async fn list(&self) -> Result<Vec<Horse>, OperatorError>;
+            async fn create(&self, resource: &Horse) -> Result<Horse, OperatorError>;
+            async fn patch(&self, resource: &Horse, patch_params: &kube::api::PatchParams, patch: &kube::api::Patch<serde_json::Value>) -> Result<Horse, OperatorError>;
+            async fn publish_event(&self, resource: &Horse, event: Event) -> Result<(), OperatorError>;
+            async fn released_ids(&self) -> Result<std::collections::HashSet<String>, OperatorError>;
+            async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+            fn get_client(&self) -> Api<Horse>;
+            fn set_client(&mut self, client: Api<Horse>);
+        }
+    }
+
+    fn setup_horse() -> Horse {
+        Horse {
+            metadata: kube::api::ObjectMeta {
+                name: Some("star".to_string()),
+                ..Default::default()
+            },
+            spec: HorseSpec {
+                name: "Star".to_string(),
+                breed: "Arabian".to_string(),
+                age: 3,
+            },
+            status: None,
+        }
+    }
+
+    fn setup_horse_dto(uuid: Uuid) -> HorseDto {
+        HorseDto {
+            uuid: Some(uuid),
+            name: "Star".to_string(),
+            breed: "Arabian".to_string(),
+            age: 3,
+        }
+    }
+
+    #[test]
+    fn test_kube_type_to_dto_copies_the_spec_and_the_uuid() {
+        let uuid = Uuid::new_v4();
+        let mut horse = setup_horse();
+        horse.status = Some(HorseStatus {
+            uuid: Some(uuid.to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(converters::kube_type_to_dto(horse), setup_horse_dto(uuid));
+    }
+
+    #[test]
+    fn test_kube_type_to_dto_without_status_has_no_uuid() {
+        let dto = converters::kube_type_to_dto(setup_horse());
+
+        assert_eq!(dto.uuid, None);
+    }
+
+    #[test]
+    fn test_dto_to_kube_type_copies_the_spec() {
+        let spec = converters::dto_to_kube_type(setup_horse_dto(Uuid::new_v4()));
+
+        assert_eq!(spec, setup_horse().spec);
+    }
+
+    #[tokio::test]
+    async fn test_remote_calls_use_the_horses_endpoints() {
+        let mut mock_horses_api = MockHorsesApi::new();
+        let uuid = Uuid::new_v4();
+        let id = uuid.to_string();
+
+        mock_horses_api
+            .expect_create_horse()
+            .times(1)
+            .returning(move |dto| {
+                Ok(HorseDto {
+                    uuid: Some(uuid),
+                    ..dto
+                })
+            });
+        mock_horses_api
+            .expect_get_horse_by_id()
+            .withf(move |id| id == uuid.to_string())
+            .times(1)
+            .returning(move |_| Ok(setup_horse_dto(uuid)));
+        mock_horses_api
+            .expect_update_horse_by_id()
+            .withf(move |id, _| id == uuid.to_string())
+            .times(1)
+            .returning(|_, dto| Ok(dto));
+        mock_horses_api
+            .expect_delete_horse_by_id()
+            .withf(move |id| id == uuid.to_string())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_horses_api
+            .expect_get_horses()
+            .times(1)
+            .returning(move || Ok(vec![setup_horse_dto(uuid)]));
+
+        let horses_api = Arc::new(mock_horses_api) as Arc<dyn HorsesApi>;
+        let dto = converters::kube_type_to_dto(setup_horse());
+
+        let created = horses_api.create(dto.clone()).await.unwrap();
+        assert_eq!(created.uuid, Some(uuid));
+        assert_eq!(
+            horses_api.get_by_id(&id).await.unwrap(),
+            setup_horse_dto(uuid)
+        );
+        assert!(horses_api.update_by_id(&id, dto).await.is_ok());
+        assert!(horses_api.delete_by_id(&id).await.is_ok());
+        assert_eq!(horses_api.list().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remote_errors_keep_their_status_code() {
+        let mut mock_horses_api = MockHorsesApi::new();
+        mock_horses_api
+            .expect_get_horse_by_id()
+            .times(1)
+            .returning(|_| {
+                Err(Error::ResponseError(ResponseContent {
+                    status: reqwest::StatusCode::NOT_FOUND,
+                    content: "Not Found".to_string(),
+                    entity: None,
+                }))
+            });
+
+        let horses_api = Arc::new(mock_horses_api) as Arc<dyn HorsesApi>;
+        let error = horses_api
+            .get_by_id(&Uuid::new_v4().to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OperatorError::FailedToGetResource(_)));
+        assert_eq!(error.class(), ErrorClass::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_creates_a_remote_horse() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_horses_api = MockHorsesApi::new();
+        let horse = Arc::new(setup_horse());
+        let uuid = Uuid::new_v4();
+
+        mock_horses_api
+            .expect_create_horse()
+            .withf(|dto| dto == &converters::kube_type_to_dto(setup_horse()))
+            .times(1)
+            .returning(move |dto| {
+                Ok(HorseDto {
+                    uuid: Some(uuid),
+                    ..dto
+                })
+            });
+        mock_horses_api
+            .expect_get_horse_by_id()
+            .times(1)
+            .returning(move |_| Ok(setup_horse_dto(uuid)));
+        kube_client
+            .expect_add_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .withf(move |horse| horse.status.as_ref().unwrap().uuid == Some(uuid.to_string()))
+            .times(2)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Created")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Horse>>;
+        let horses_api = Arc::new(mock_horses_api) as Arc<dyn HorsesApi>;
+
+        let result = reconcile(
+            Arc::clone(&horse),
+            Arc::new(ContextData::new(kube_client.clone(), horses_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use kube::{
+        api::ObjectMeta,
+        runtime::events::{Event, EventType},
+        Api,
+    };
+    use mockall::mock;
+    use openapi::apis::cats_api::{
+        CreateCatError, DeleteCatByIdError, GetCatByIdError, GetCatsError, UpdateCatByIdError,
+    };
+    use openapi::{apis::cats_api::CatsApi, apis::Error, models::Cat as CatDto};
+    use operator::{
+        controllers::{
+            importer::{import, imported_name, ImportReport, IMPORTED_LABEL},
+            reconciler::REMOTE_ID_ANNOTATION,
+        },
+        errors::OperatorError,
+        types::cat::{Cat, CatSpec, CatStatus},
+        KubeApi,
+    };
+    use std::{collections::HashSet, sync::Arc};
+    use uuid::Uuid;
+
+    mock! {
+        pub CatsApi {}
+
+        #[async_trait]
+        impl CatsApi for CatsApi {
+            async fn create_cat<'cat>(&self, cat: CatDto) -> Result<CatDto, Error<CreateCatError>>;
+            async fn delete_cat_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteCatByIdError>>;
+            async fn get_cat_by_id<'id>(&self, id: &'id str) -> Result<CatDto, Error<GetCatByIdError>>;
+            async fn update_cat_by_id<'id, 'cat>(&self, id: &'id str, cat: CatDto) -> Result<CatDto, Error<UpdateCatByIdError>>;
+            async fn get_cats(&self) -> Result<Vec<CatDto>, Error<GetCatsError>>;
+        }
+    }
+
+    mock! {
+        pub KubeApiClient {}
+
+        #[async_trait]
+        impl KubeApi<Cat> for KubeApiClient {
+            async fn add_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn remove_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn update_status(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn list(&self) -> Result<Vec<Cat>, OperatorError>;
+            async fn create(&self, resource: &Cat) -> Result<Cat, OperatorError>;
+            async fn patch(&self, resource: &Cat, patch_params: &kube::api::PatchParams, patch: &kube::api::Patch<serde_json::Value>) -> Result<Cat, OperatorError>;
+            async fn publish_event(&self, resource: &Cat, event: Event) -> Result<(), OperatorError>;
+            async fn released_ids(&self) -> Result<std::collections::HashSet<String>, OperatorError>;
+            async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+            fn get_client(&self) -> Api<Cat>;
+            fn set_client(&mut self, client: Api<Cat>);
+        }
+    }
+
+    fn remote_cat(uuid: Uuid, name: &str) -> CatDto {
+        CatDto {
+            uuid: Some(uuid),
+            name: name.to_string(),
+            breed: "Siamese".to_string(),
+            age: 3,
+        }
+    }
+
+    fn cat(name: &str, uuid: Option<Uuid>, remote_id: Option<Uuid>) -> Cat {
+        Cat {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("default".to_string()),
+                annotations: remote_id.map(|remote_id| {
+                    [(REMOTE_ID_ANNOTATION.to_string(), remote_id.to_string())]
+                        .into_iter()
+                        .collect()
+                }),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: name.to_string(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            },
+            status: Some(CatStatus {
+                uuid: uuid.map(|uuid| uuid.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_creates_cats_for_unreferenced_remote_cats() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let managed = Uuid::new_v4();
+        let adopting = Uuid::new_v4();
+        let unmanaged = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(managed, "Whiskers"),
+                remote_cat(adopting, "Tom"),
+                remote_cat(unmanaged, "Felix"),
+            ])
+        });
+        kube_client
+            .expect_released_ids()
+            .returning(|| Ok(HashSet::new()));
+        kube_client.expect_list().times(1).returning(move || {
+            Ok(vec![
+                cat("whiskers", Some(managed), None),
+                cat("tom", None, Some(adopting)),
+            ])
+        });
+        kube_client
+            .expect_create()
+            .withf(move |cat: &Cat| {
+                let annotations = cat.metadata.annotations.as_ref().unwrap();
+                let labels = cat.metadata.labels.as_ref().unwrap();
+                cat.metadata.name == Some(imported_name("Cat", &unmanaged.to_string()))
+                    && cat.metadata.namespace.as_deref() == Some("pets")
+                    && cat.spec.name == "Felix"
+                    && annotations.get(REMOTE_ID_ANNOTATION) == Some(&unmanaged.to_string())
+                    && labels.get(IMPORTED_LABEL).map(String::as_str) == Some("true")
+            })
+            .times(1)
+            .returning(|cat| Ok(cat.clone()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Imported")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_clients = vec![Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>];
+
+        let confirmed = HashSet::from([unmanaged.to_string()]);
+
+        let report = import(&kube_clients, cats_api.as_ref(), "pets", &confirmed)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                unreferenced: vec![unmanaged.to_string()],
+                imported: vec![unmanaged.to_string()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_cats_that_were_already_imported() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let uuid = Uuid::new_v4();
+
+        mock_cats_api
+            .expect_get_cats()
+            .times(1)
+            .returning(move || Ok(vec![remote_cat(uuid, "Felix")]));
+        kube_client
+            .expect_released_ids()
+            .returning(|| Ok(HashSet::new()));
+        kube_client.expect_list().times(1).returning(|| Ok(vec![]));
+        kube_client.expect_create().times(1).returning(|_| {
+            Err(OperatorError::FailedToCreateResource(
+                kube::Error::Api(kube::error::ErrorResponse {
+                    status: "Failure".to_string(),
+                    message: "already exists".to_string(),
+                    reason: "AlreadyExists".to_string(),
+                    code: 409,
+                })
+                .into(),
+            ))
+        });
+        kube_client.expect_publish_event().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_clients = vec![Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>];
+
+        let confirmed = HashSet::from([uuid.to_string()]);
+
+        let report = import(&kube_clients, cats_api.as_ref(), "default", &confirmed)
+            .await
+            .unwrap();
+
+        assert!(report.imported.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_waits_for_a_second_pass_before_importing() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let just_created = Uuid::new_v4();
+
+        mock_cats_api
+            .expect_get_cats()
+            .times(1)
+            .returning(move || Ok(vec![remote_cat(just_created, "Felix")]));
+        kube_client
+            .expect_released_ids()
+            .returning(|| Ok(HashSet::new()));
+        kube_client.expect_list().times(1).returning(|| Ok(vec![]));
+        kube_client.expect_create().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_clients = vec![Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>];
+
+        let report = import(&kube_clients, cats_api.as_ref(), "default", &HashSet::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                unreferenced: vec![just_created.to_string()],
+                imported: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_released_cats() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let retained = Uuid::new_v4();
+        let orphaned = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(retained, "Felix"),
+                remote_cat(orphaned, "Tom"),
+            ])
+        });
+        kube_client
+            .expect_released_ids()
+            .returning(move || Ok(HashSet::from([retained.to_string(), orphaned.to_string()])));
+        kube_client.expect_list().times(1).returning(|| Ok(vec![]));
+        kube_client.expect_create().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_clients = vec![Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>];
+
+        let confirmed = HashSet::from([retained.to_string(), orphaned.to_string()]);
+
+        let report = import(&kube_clients, cats_api.as_ref(), "default", &confirmed)
+            .await
+            .unwrap();
+
+        assert_eq!(report, ImportReport::default());
+    }
+}
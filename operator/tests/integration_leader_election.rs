@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use k8s_openapi::{
+        api::coordination::v1::LeaseSpec, apimachinery::pkg::apis::meta::v1::MicroTime,
+    };
+    use operator::leader_election::{is_lease_expired, LeaderElector};
+    use tokio::net::TcpListener;
+
+    fn setup_lease_spec(renewed_seconds_ago: i64) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some("operator-0".to_string()),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(MicroTime(
+                Utc::now() - Duration::seconds(renewed_seconds_ago),
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lease_renewed_within_duration_is_not_expired() {
+        let spec = setup_lease_spec(5);
+
+        assert!(!is_lease_expired(&spec, Utc::now()));
+    }
+
+    #[test]
+    fn test_lease_not_renewed_within_duration_is_expired() {
+        let spec = setup_lease_spec(20);
+
+        assert!(is_lease_expired(&spec, Utc::now()));
+    }
+
+    #[test]
+    fn test_released_lease_is_expired() {
+        let spec = LeaseSpec {
+            holder_identity: None,
+            ..setup_lease_spec(0)
+        };
+
+        assert!(is_lease_expired(&spec, Utc::now()));
+    }
+
+    #[test]
+    fn test_lease_without_renew_time_is_expired() {
+        let spec = LeaseSpec {
+            renew_time: None,
+            ..setup_lease_spec(0)
+        };
+
+        assert!(is_lease_expired(&spec, Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn test_hold_steps_down_when_a_renewal_hangs() {
+        // Accepts connections but never answers, like an API server that stalls.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+        let client = kube::Client::try_from(kube::Config::new(url.parse().unwrap())).unwrap();
+        let elector = LeaderElector::new(
+            client,
+            "default",
+            "operator-leader",
+            "operator-0",
+            std::time::Duration::from_secs(3),
+        );
+
+        let held = tokio::time::timeout(std::time::Duration::from_secs(5), elector.hold()).await;
+
+        assert!(held.is_ok());
+    }
+}
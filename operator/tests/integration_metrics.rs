@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use operator::{errors::OperatorError, metrics::METRICS};
+
+    #[tokio::test]
+    async fn test_reconcile_outcome_is_recorded() {
+        let timer = METRICS.reconcile_started("MetricsTestKind");
+        timer.finish::<()>(&Err(OperatorError::ResourceNotFound("cat".to_string())));
+
+        let rendered = METRICS.render();
+
+        assert!(rendered.contains(
+            r#"operator_reconciliations_total{kind="MetricsTestKind",result="error"} 1"#
+        ));
+        assert!(rendered.contains(
+            r#"operator_errors_total{error="ResourceNotFound",kind="MetricsTestKind"} 1"#
+        ));
+        assert!(rendered.contains(r#"operator_reconciles_in_flight{kind="MetricsTestKind"} 0"#));
+    }
+
+    #[tokio::test]
+    async fn test_remote_call_latency_is_recorded_per_method() {
+        let result: Result<(), ()> = METRICS
+            .observe_remote_call("metrics_test_get_cat_by_id", async { Ok(()) })
+            .await;
+
+        assert!(result.is_ok());
+        assert!(METRICS.render().contains(
+            r#"operator_remote_api_request_duration_seconds_count{method="metrics_test_get_cat_by_id",result="success"} 1"#
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_queue_depth_counts_waiting_objects_once() {
+        METRICS.reconcile_queued("QueueTestKind", "default/whiskers");
+        METRICS.reconcile_queued("QueueTestKind", "default/whiskers");
+        METRICS.reconcile_queued("QueueTestKind", "default/felix");
+
+        assert!(METRICS
+            .render()
+            .contains(r#"operator_queue_depth{kind="QueueTestKind"} 2"#));
+
+        METRICS.reconcile_dequeued("QueueTestKind", "default/whiskers");
+
+        assert!(METRICS
+            .render()
+            .contains(r#"operator_queue_depth{kind="QueueTestKind"} 1"#));
+
+        METRICS.clear_queue("QueueTestKind");
+
+        assert!(METRICS
+            .render()
+            .contains(r#"operator_queue_depth{kind="QueueTestKind"} 0"#));
+    }
+}
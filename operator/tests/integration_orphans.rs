@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use kube::{api::ObjectMeta, runtime::events::Event, Api};
+    use mockall::mock;
+    use openapi::apis::cats_api::{
+        CreateCatError, DeleteCatByIdError, GetCatByIdError, GetCatsError, UpdateCatByIdError,
+    };
+    use openapi::{apis::cats_api::CatsApi, apis::Error, models::Cat as CatDto};
+    use operator::{
+        config::OrphanPolicy,
+        controllers::orphans::{sweep, SweepReport},
+        errors::OperatorError,
+        types::cat::{Cat, CatSpec, CatStatus},
+        KubeApi,
+    };
+    use std::{collections::HashSet, sync::Arc};
+    use uuid::Uuid;
+
+    mock! {
+        pub CatsApi {}
+
+        #[async_trait]
+        impl CatsApi for CatsApi {
+            async fn create_cat<'cat>(&self, cat: CatDto) -> Result<CatDto, Error<CreateCatError>>;
+            async fn delete_cat_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteCatByIdError>>;
+            async fn get_cat_by_id<'id>(&self, id: &'id str) -> Result<CatDto, Error<GetCatByIdError>>;
+            async fn update_cat_by_id<'id, 'cat>(&self, id: &'id str, cat: CatDto) -> Result<CatDto, Error<UpdateCatByIdError>>;
+            async fn get_cats(&self) -> Result<Vec<CatDto>, Error<GetCatsError>>;
+        }
+    }
+
+    mock! {
+        pub KubeApiClient {}
+
+        #[async_trait]
+        impl KubeApi<Cat> for KubeApiClient {
+            async fn add_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn remove_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn update_status(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn list(&self) -> Result<Vec<Cat>, OperatorError>;
+            async fn create(&self, resource: &Cat) -> Result<Cat, OperatorError>;
+            async fn patch(&self, resource: &Cat, patch_params: &kube::api::PatchParams, patch: &kube::api::Patch<serde_json::Value>) -> Result<Cat, OperatorError>;
+            async fn publish_event(&self, resource: &Cat, event: Event) -> Result<(), OperatorError>;
+            async fn released_ids(&self) -> Result<std::collections::HashSet<String>, OperatorError>;
+            async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+            fn get_client(&self) -> Api<Cat>;
+            fn set_client(&mut self, client: Api<Cat>);
+        }
+    }
+
+    fn remote_cat(uuid: Uuid, name: &str) -> CatDto {
+        CatDto {
+            uuid: Some(uuid),
+            name: name.to_string(),
+            breed: "Siamese".to_string(),
+            age: 3,
+        }
+    }
+
+    fn managed_cat(uuid: Uuid) -> Cat {
+        Cat {
+            metadata: ObjectMeta {
+                name: Some("whiskers".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: "Whiskers".to_string(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            },
+            status: Some(CatStatus {
+                uuid: Some(uuid.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn kube_client_managing(uuid: Uuid, released: Vec<Uuid>) -> MockKubeApiClient {
+        let mut kube_client = MockKubeApiClient::new();
+        kube_client
+            .expect_list()
+            .returning(move || Ok(vec![managed_cat(uuid)]));
+        kube_client
+            .expect_released_ids()
+            .returning(move || Ok(released.iter().map(Uuid::to_string).collect()));
+        kube_client
+    }
+
+    fn kube_clients_managing(uuid: Uuid) -> Vec<Arc<dyn KubeApi<Cat> + Send + Sync>> {
+        vec![Arc::new(kube_client_managing(uuid, vec![]))]
+    }
+
+    #[tokio::test]
+    async fn test_sweep_reports_orphans_without_changing_them() {
+        let mut mock_cats_api = MockCatsApi::new();
+        let managed = Uuid::new_v4();
+        let orphan = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(managed, "Whiskers"),
+                remote_cat(orphan, "Felix"),
+            ])
+        });
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        mock_cats_api.expect_delete_cat_by_id().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let confirmed = HashSet::from([orphan.to_string()]);
+
+        let report = sweep(
+            &kube_clients_managing(managed),
+            cats_api.as_ref(),
+            OrphanPolicy::Report,
+            &confirmed,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            report,
+            SweepReport {
+                orphans: vec![orphan.to_string()],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sweep_deletes_only_orphans_found_by_the_previous_sweep() {
+        let mut mock_cats_api = MockCatsApi::new();
+        let managed = Uuid::new_v4();
+        let confirmed_orphan = Uuid::new_v4();
+        let new_orphan = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(managed, "Whiskers"),
+                remote_cat(confirmed_orphan, "Felix"),
+                remote_cat(new_orphan, "Tom"),
+            ])
+        });
+        mock_cats_api
+            .expect_delete_cat_by_id()
+            .withf(move |id| id == confirmed_orphan.to_string())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let confirmed = HashSet::from([confirmed_orphan.to_string()]);
+
+        let report = sweep(
+            &kube_clients_managing(managed),
+            cats_api.as_ref(),
+            OrphanPolicy::Delete,
+            &confirmed,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            report.orphans,
+            vec![confirmed_orphan.to_string(), new_orphan.to_string()]
+        );
+        assert_eq!(report.deleted, vec![confirmed_orphan.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_tags_orphans_once_without_changing_them() {
+        let mut mock_cats_api = MockCatsApi::new();
+        let managed = Uuid::new_v4();
+        let untagged = Uuid::new_v4();
+        let tagged = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(untagged, "Felix"),
+                remote_cat(tagged, "Tom"),
+            ])
+        });
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        let mut kube_client = kube_client_managing(managed, vec![tagged]);
+        kube_client
+            .expect_record_released()
+            .withf(move |uuid, _| uuid == untagged.to_string())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let confirmed = HashSet::from([untagged.to_string(), tagged.to_string()]);
+
+        let report = sweep(
+            &[Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>],
+            cats_api.as_ref(),
+            OrphanPolicy::Tag,
+            &confirmed,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.orphans, vec![untagged.to_string()]);
+        assert_eq!(report.tagged, vec![untagged.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_leaves_released_cats_alone() {
+        let mut mock_cats_api = MockCatsApi::new();
+        let managed = Uuid::new_v4();
+        let retained = Uuid::new_v4();
+        let orphaned = Uuid::new_v4();
+
+        mock_cats_api.expect_get_cats().times(1).returning(move || {
+            Ok(vec![
+                remote_cat(retained, "Felix"),
+                remote_cat(orphaned, "Tom"),
+            ])
+        });
+        mock_cats_api.expect_delete_cat_by_id().times(0);
+        let kube_client = kube_client_managing(managed, vec![retained, orphaned]);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let confirmed = HashSet::from([retained.to_string(), orphaned.to_string()]);
+
+        let report = sweep(
+            &[Arc::new(kube_client) as Arc<dyn KubeApi<Cat> + Send + Sync>],
+            cats_api.as_ref(),
+            OrphanPolicy::Delete,
+            &confirmed,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report, SweepReport::default());
+    }
+}
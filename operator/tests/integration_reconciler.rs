@@ -0,0 +1,1470 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+    use kube::{
+        api::Patch,
+        core::ErrorResponse,
+        runtime::events::{Event, EventType},
+        Api,
+    };
+    use mockall::mock;
+    use openapi::apis::cats_api::{
+        CreateCatError, DeleteCatByIdError, GetCatByIdError, GetCatsError, UpdateCatByIdError,
+    };
+    use openapi::apis::ResponseContent;
+    use openapi::{apis::cats_api::CatsApi, apis::Error, models::Cat as CatDto};
+    use operator::{
+        controllers::{
+            cats::{converters, ContextData},
+            conditions::{
+                DRIFTED_CONDITION, ERROR_CONDITION, LOST_CONDITION, READY_CONDITION,
+                STALLED_CONDITION, SUSPENDED_CONDITION, SYNCED_CONDITION,
+            },
+            reconciler::{
+                check_for_drift, handle_create, handle_delete, reconcile,
+                DELETION_POLICY_ANNOTATION, DRIFT_POLICY_ANNOTATION, REMOTE_ID_ANNOTATION,
+                SUSPEND_ANNOTATION,
+            },
+        },
+        errors::{ErrorClass, OperatorError},
+        types::{
+            cat::{Cat, CatSpec, CatStatus},
+            status::{DriftReport, FieldDrift},
+        },
+        KubeApi,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use uuid::Uuid;
+
+    mock! {
+        pub CatsApi {}
+
+        #[async_trait]
+        impl CatsApi for CatsApi {
+            async fn create_cat<'cat>(&self, cat: CatDto) -> Result<CatDto, Error<CreateCatError>>;
+            async fn delete_cat_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteCatByIdError>>;
+            async fn get_cat_by_id<'id>(&self, id: &'id str) -> Result<CatDto, Error<GetCatByIdError>>;
+            async fn update_cat_by_id<'id, 'cat>(&self, id: &'id str, cat: CatDto) -> Result<CatDto, Error<UpdateCatByIdError>>;
+            async fn get_cats(&self) -> Result<Vec<CatDto>, Error<GetCatsError>>;
+        }
+    }
+
+    mock! {
+        pub KubeApiClient {}
+
+        #[async_trait]
+        impl KubeApi<Cat> for KubeApiClient {
+            async fn add_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn remove_finalizer(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn update_status(&self, resource: &mut Cat) -> Result<(), OperatorError>;
+            async fn list(&self) -> Result<Vec<Cat>, OperatorError>;
+            async fn create(&self, resource: &Cat) -> Result<Cat, OperatorError>;
+            async fn patch(&self, resource: &Cat, patch_params: &kube::api::PatchParams, patch: &kube::api::Patch<serde_json::Value>) -> Result<Cat, OperatorError>;
+            async fn publish_event(&self, resource: &Cat, event: Event) -> Result<(), OperatorError>;
+            async fn released_ids(&self) -> Result<std::collections::HashSet<String>, OperatorError>;
+            async fn record_released(&self, uuid: &str, reason: &str) -> Result<(), OperatorError>;
+            fn get_client(&self) -> Api<Cat>;
+            fn set_client(&mut self, client: Api<Cat>);
+        }
+    }
+
+    fn find_condition<'a>(status: &'a CatStatus, type_: &str) -> &'a Condition {
+        status
+            .conditions
+            .iter()
+            .find(|condition| condition.type_ == type_)
+            .unwrap_or_else(|| panic!("no {} condition", type_))
+    }
+
+    fn synced_condition() -> Condition {
+        Condition {
+            status: "True".to_string(),
+            type_: SYNCED_CONDITION.to_string(),
+            reason: "Created".to_string(),
+            message: "The remote cat was created".to_string(),
+            observed_generation: Some(1),
+            last_transition_time: Time(chrono::Utc::now()),
+        }
+    }
+
+    fn setup_cat() -> Cat {
+        Cat {
+            metadata: kube::api::ObjectMeta {
+                name: Some("whiskers".to_string()),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: "Whiskers".to_string(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            },
+            status: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_create_success() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let mut cat = setup_cat();
+
+        let remote_cat = CatDto {
+            uuid: Some(Uuid::new_v4()),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age,
+        };
+
+        let expected_name = cat.spec.name.clone();
+        let expected_breed = cat.spec.breed.clone();
+        let expected_age = cat.spec.age;
+
+        mock_cats_api
+            .expect_create_cat()
+            .withf(move |dto| {
+                dto.name == expected_name && dto.breed == expected_breed && dto.age == expected_age
+            })
+            .times(1)
+            .returning(move |dto| {
+                Ok(CatDto {
+                    uuid: remote_cat.uuid,
+                    ..dto
+                })
+            });
+
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_add_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        // kube_client
+        //     .expect_create_condition()
+        //     .times(1)
+        //     .returning(|_, _, _, _, _| Condition {
+        //         last_transition_time: Time(chrono::Utc::now()),
+        //         message: "The cat has been created successfully".to_string(),
+        //         observed_generation: Some(0),
+        //         reason: "CatCreated".to_string(),
+        //         status: "True".to_string(),
+        //         type_: "AvailableCreated".to_string(),
+        //     });
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Created")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        // Now it's safe to borrow `cat` mutably without partial moves
+        let result = handle_create(kube_client.as_ref(), cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+        assert!(cat.status.is_some());
+
+        let status = cat.status.as_ref().unwrap();
+        assert_eq!(status.uuid, converters::uuid_to_string(remote_cat.uuid));
+        assert_eq!(status.conditions.len(), 2);
+
+        let synced = find_condition(status, SYNCED_CONDITION);
+        assert_eq!(synced.status, "True");
+        assert_eq!(synced.reason, "Created");
+        assert!(synced.last_transition_time.0.timestamp() > 0);
+
+        let ready = find_condition(status, READY_CONDITION);
+        assert_eq!(ready.status, "True");
+        assert_eq!(ready.reason, "Synced");
+    }
+
+    #[tokio::test]
+    async fn test_handle_create_failed() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let mut cat = setup_cat();
+
+        mock_cats_api.expect_create_cat().times(1).returning(|_| {
+            Err(Error::ResponseError(ResponseContent {
+                status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                content: "Internal Server Error".to_string(),
+                entity: None,
+            }))
+        });
+
+        // kube_client
+        //     .expect_create_condition()
+        //     .times(1)
+        //     .returning(|_, _, _, _, _| Condition {
+        //         status: "False".to_string(),
+        //         type_: "AvailableNotCreated".to_string(),
+        //         reason: "CatNotCreated".to_string(),
+        //         message: "Failed to create the cat".to_string(),
+        //         observed_generation: Some(0),
+        //         last_transition_time: Time(chrono::Utc::now()),
+        //     });
+
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "CreateFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+
+        let result = handle_create(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_err());
+        assert!(cat.status.is_some());
+        let status = cat.status.as_ref().unwrap();
+        assert!(status.uuid.is_none());
+        assert_eq!(find_condition(status, SYNCED_CONDITION).status, "False");
+        assert_eq!(find_condition(status, ERROR_CONDITION).status, "True");
+        assert_eq!(find_condition(status, READY_CONDITION).status, "False");
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_new_resource() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let cat = Arc::new(setup_cat());
+
+        let remote_cat = CatDto {
+            uuid: Some(Uuid::new_v4()),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age,
+        };
+
+        let remote_cat_clone = remote_cat.clone();
+
+        let expected_name = cat.spec.name.clone();
+        let expected_breed = cat.spec.breed.clone();
+        let expected_age = cat.spec.age;
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+
+        mock_cats_api
+            .expect_create_cat()
+            .withf(move |dto| {
+                dto.name == expected_name && dto.breed == expected_breed && dto.age == expected_age
+            })
+            .times(1)
+            .returning(move |dto| {
+                Ok(CatDto {
+                    uuid: remote_cat_clone.uuid,
+                    ..dto
+                })
+            });
+
+        kube_client
+            .expect_add_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // kube_client
+        //     .expect_create_condition()
+        //     .times(1)
+        //     .withf(|status, type_, reason, message, observed_generation| {
+        //         status == "Created"
+        //             && type_ == "AvailableCreated"
+        //             && reason == "Created the resource"
+        //             && message == "Resource has been created"
+        //             && *observed_generation == None
+        //     })
+        //     .returning(
+        //         |status, type_, reason, message, observed_generation| Condition {
+        //             status: status.to_string(),
+        //             type_: type_.to_string(),
+        //             reason: reason.to_string(),
+        //             message: message.to_string(),
+        //             observed_generation: observed_generation,
+        //             last_transition_time: Time(chrono::Utc::now()),
+        //         },
+        //     );
+
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .withf(|cat| {
+                cat.status.is_some()
+                    && cat.status.as_ref().unwrap().deletion_policy.is_none()
+                    && cat.status.as_ref().unwrap().uuid.is_some()
+                    && cat.status.as_ref().unwrap().observed_generation.is_none()
+            })
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .withf(|cat| cat.status.as_ref().unwrap().deletion_policy.as_deref() == Some("Delete"))
+            .returning(|_| Ok(()));
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Created")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_failed_to_create_resource_because_of_internal_server_error() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let cat = Arc::new(setup_cat());
+
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        mock_cats_api.expect_create_cat().times(1).returning(|_| {
+            Err(Error::ResponseError(ResponseContent {
+                status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                content: "Internal Server Error".to_string(),
+                entity: None,
+            }))
+        });
+
+        // kube_client
+        //     .expect_create_condition()
+        //     .times(1)
+        //     .returning(|_, _, _, _, _| Condition {
+        //         status: "Failed".to_string(),
+        //         type_: "AvailableFailed".to_string(),
+        //         reason: "CatNotCreated".to_string(),
+        //         message: "Resource has not been created".to_string(),
+        //         observed_generation: Some(0),
+        //         last_transition_time: Time(chrono::Utc::now()),
+        //     });
+
+        kube_client
+            .expect_update_status()
+            .withf(|cat| cat.status.as_ref().unwrap().sync.consecutive_failures == 0)
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .withf(|cat| {
+                let sync = &cat.status.as_ref().unwrap().sync;
+                sync.consecutive_failures == 1
+                    && sync.last_error_code == Some(500)
+                    && sync.last_error_message.is_some()
+                    && sync.last_attempt_time.is_some()
+                    && sync.last_sync_time.is_none()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "CreateFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_marks_resource_stalled_on_permanent_failure() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let cat = Arc::new(setup_cat());
+
+        mock_cats_api.expect_create_cat().times(1).returning(|_| {
+            Err(Error::ResponseError(ResponseContent {
+                status: reqwest::StatusCode::BAD_REQUEST,
+                content: "Bad Request".to_string(),
+                entity: None,
+            }))
+        });
+
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .withf(|cat| {
+                cat.status
+                    .as_ref()
+                    .unwrap()
+                    .conditions
+                    .iter()
+                    .all(|c| c.type_ != STALLED_CONDITION)
+            })
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .times(1)
+            .withf(|cat| {
+                cat.status
+                    .as_ref()
+                    .unwrap()
+                    .conditions
+                    .iter()
+                    .any(|c| c.type_ == STALLED_CONDITION && c.status == "True")
+            })
+            .returning(|_| Ok(()));
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "CreateFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "Stalled")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().class(), ErrorClass::Permanent);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_skips_stalled_resource_until_it_changes() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+
+        let mut cat = setup_cat();
+        cat.metadata.generation = Some(2);
+        cat.status = Some(CatStatus {
+            conditions: vec![Condition {
+                status: "True".to_string(),
+                type_: STALLED_CONDITION.to_string(),
+                message: "Bad Request".to_string(),
+                reason: "FailedToCreateResource".to_string(),
+                last_transition_time: Time(chrono::Utc::now()),
+                observed_generation: Some(2),
+            }],
+            ..Default::default()
+        });
+        let cat = Arc::new(cat);
+
+        mock_cats_api.expect_create_cat().times(0);
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        kube_client.expect_update_status().times(0);
+        kube_client.expect_publish_event().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_not_executing_creation_nor_update_if_observation_generation_and_meta_observation_equal(
+    ) {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let uuid = Uuid::new_v4();
+
+        let cat = Arc::new(Cat {
+            metadata: kube::api::ObjectMeta {
+                name: Some("whiskers".to_string()),
+                generation: Some(1),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: "Whiskers".to_string(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            },
+            status: Some(CatStatus {
+                uuid: Some(uuid.to_string()),
+                observed_generation: Some(1),
+                conditions: vec![synced_condition()],
+                deletion_policy: Some("Delete".to_string()),
+                ..Default::default()
+            }),
+        });
+
+        let remote_cat = CatDto {
+            uuid: Some(uuid),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age,
+        };
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_create_cat().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        // kube_client.expect_create_condition().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat| {
+                let sync = &cat.status.as_ref().unwrap().sync;
+                sync.last_sync_time.is_some() && sync.consecutive_failures == 0
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client.expect_add_finalizer().times(0);
+
+        kube_client.expect_publish_event().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_removes_the_legacy_conditions() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, mut remote_cat) = setup_drifted_cat(None);
+        remote_cat.age = cat.spec.age;
+        let legacy = ["AvailableCreated", "AvailableUpdated", "AvailableFailed"];
+        cat.status.as_mut().unwrap().conditions = legacy
+            .iter()
+            .map(|type_| Condition {
+                type_: type_.to_string(),
+                ..synced_condition()
+            })
+            .chain(std::iter::once(synced_condition()))
+            .collect();
+        let cat = Arc::new(cat);
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        kube_client
+            .expect_update_status()
+            .withf(move |cat| {
+                let conditions = &cat.status.as_ref().unwrap().conditions;
+                conditions
+                    .iter()
+                    .all(|condition| !legacy.contains(&condition.type_.as_str()))
+                    && conditions
+                        .iter()
+                        .any(|condition| condition.type_ == SYNCED_CONDITION)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client.expect_publish_event().returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_executing_update_if_observation_generation_and_meta_observation_not_equal(
+    ) {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let uuid = Uuid::new_v4();
+
+        let cat = Arc::new(Cat {
+            metadata: kube::api::ObjectMeta {
+                name: Some("whiskers".to_string()),
+                generation: Some(1),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: "Whiskers".to_string(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            },
+            status: Some(CatStatus {
+                uuid: Some(uuid.to_string()),
+                observed_generation: Some(0),
+                conditions: vec![],
+                deletion_policy: Some("Delete".to_string()),
+                ..Default::default()
+            }),
+        });
+
+        let remote_cat = CatDto {
+            uuid: Some(uuid),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age,
+        };
+
+        let cat_clone_1 = Arc::clone(&cat);
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_create_cat().times(0);
+
+        mock_cats_api
+            .expect_update_cat_by_id()
+            .withf(move |id, dto| {
+                id == uuid.to_string()
+                    && dto.name == cat_clone_1.spec.name
+                    && dto.breed == cat_clone_1.spec.breed
+                    && dto.age == cat_clone_1.spec.age
+            })
+            .times(1)
+            .returning(move |_, dto| Ok(dto));
+
+        kube_client.expect_patch().times(0);
+        kube_client
+            .expect_update_status()
+            .times(2)
+            .returning(|_| Ok(()));
+        // kube_client
+        //     .expect_create_condition()
+        //     .times(1)
+        //     .withf(move |status, type_, reason, message, observed_generation| {
+        //         status == "Updated"
+        //             && type_ == "AvailableUpdated"
+        //             && reason == "Updated the resource"
+        //             && message == "Resource has been updated"
+        //             && *observed_generation == Some(1i64)
+        //     })
+        //     .returning(
+        //         |status, type_, reason, message, observed_generation| Condition {
+        //             status: status.to_string(),
+        //             type_: type_.to_string(),
+        //             reason: reason.to_string(),
+        //             message: message.to_string(),
+        //             observed_generation: observed_generation,
+        //             last_transition_time: Time(chrono::Utc::now()),
+        //         },
+        //     );
+
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Updated")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    fn setup_drifted_cat(policy: Option<&str>) -> (Cat, CatDto) {
+        let uuid = Uuid::new_v4();
+        let mut cat = setup_cat();
+        cat.metadata.generation = Some(1);
+        cat.metadata.annotations = policy.map(|policy| {
+            [(DRIFT_POLICY_ANNOTATION.to_string(), policy.to_string())]
+                .into_iter()
+                .collect()
+        });
+        cat.status = Some(CatStatus {
+            uuid: Some(uuid.to_string()),
+            observed_generation: Some(1),
+            conditions: vec![],
+            ..Default::default()
+        });
+        let remote_cat = CatDto {
+            uuid: Some(uuid),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age + 1,
+        };
+        (cat, remote_cat)
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_enforces_the_spec_by_default() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, remote_cat) = setup_drifted_cat(None);
+        let expected_age = cat.spec.age;
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api
+            .expect_update_cat_by_id()
+            .withf(move |_, dto| dto.age == expected_age)
+            .times(1)
+            .returning(|_, dto| Ok(dto));
+        kube_client.expect_patch().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat: &Cat| {
+                cat.status
+                    .as_ref()
+                    .unwrap()
+                    .last_drift
+                    .as_ref()
+                    .is_some_and(|drift| drift.corrected)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| {
+                event.reason == "DriftCorrected"
+                    && event
+                        .note
+                        .as_ref()
+                        .unwrap()
+                        .ends_with("age (desired 3, remote 4)")
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+        let drift = cat.status.unwrap().last_drift.unwrap();
+        assert_eq!(drift.policy, "enforce");
+        assert!(drift.corrected);
+        assert!(drift.detected_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_only_reports_with_report_only_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, remote_cat) = setup_drifted_cat(Some("report-only"));
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client.expect_patch().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat: &Cat| {
+                cat.status
+                    .as_ref()
+                    .unwrap()
+                    .conditions
+                    .iter()
+                    .any(|condition| {
+                        condition.type_ == DRIFTED_CONDITION && condition.status == "True"
+                    })
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "DriftDetected")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+        let status = cat.status.unwrap();
+        let condition = &status.conditions[0];
+        assert_eq!(condition.type_, DRIFTED_CONDITION);
+        assert_eq!(condition.reason, "DriftDetected");
+        let drift = status.last_drift.unwrap();
+        assert_eq!(drift.policy, "report-only");
+        assert!(!drift.corrected);
+        assert_eq!(
+            drift.fields,
+            vec![FieldDrift {
+                field: "age".to_string(),
+                desired: "3".to_string(),
+                remote: "4".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_reports_a_drift_only_once_with_report_only_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, remote_cat) = setup_drifted_cat(Some("report-only"));
+        let remote_spec = converters::dto_to_kube_type(remote_cat.clone());
+        let status = cat.status.as_mut().unwrap();
+        status.last_drift = Some(DriftReport::between(
+            &cat.spec,
+            &remote_spec,
+            "report-only",
+            Time(chrono::Utc::now()),
+        ));
+        status.conditions = vec![Condition {
+            type_: DRIFTED_CONDITION.to_string(),
+            status: "True".to_string(),
+            reason: "DriftDetected".to_string(),
+            message: "The remote Cat differs from the spec".to_string(),
+            observed_generation: Some(1),
+            last_transition_time: Time(chrono::Utc::now()),
+        }];
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client.expect_update_status().times(0);
+        kube_client.expect_publish_event().times(0);
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_copies_remote_values_with_adopt_remote_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, remote_cat) = setup_drifted_cat(Some("adopt-remote"));
+        cat.metadata.resource_version = Some("7".to_string());
+        let remote_age = remote_cat.age;
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client
+            .expect_patch()
+            .withf(move |_, _, patch| {
+                matches!(patch, Patch::Merge(patch)
+                    if patch["spec"]["age"] == remote_age
+                        && patch["metadata"]["resourceVersion"] == "7")
+            })
+            .times(1)
+            .returning(move |cat, _, _| {
+                let mut cat = cat.clone();
+                cat.spec.age = remote_age;
+                cat.metadata.generation = Some(2);
+                Ok(cat)
+            });
+        kube_client
+            .expect_update_status()
+            .withf(|cat: &Cat| cat.status.as_ref().unwrap().observed_generation == Some(2))
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "DriftAdopted")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+        assert_eq!(cat.spec.age, remote_age);
+        assert_eq!(cat.metadata.generation, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_does_not_adopt_remote_values_into_a_changed_spec() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, remote_cat) = setup_drifted_cat(Some("adopt-remote"));
+        let expected_age = cat.spec.age;
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(move |_| Ok(remote_cat.clone()));
+        kube_client.expect_patch().times(1).returning(|_, _, _| {
+            Err(OperatorError::FailedToPatchResource(
+                kube::Error::Api(ErrorResponse {
+                    status: "Failure".to_string(),
+                    message: "the object has been modified".to_string(),
+                    reason: "Conflict".to_string(),
+                    code: 409,
+                })
+                .into(),
+            ))
+        });
+        kube_client.expect_update_status().times(0);
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.reason == "DriftAdoptionFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert_eq!(result.unwrap_err().class(), ErrorClass::Conflict);
+        assert_eq!(cat.spec.age, expected_age);
+    }
+
+    fn setup_cat_to_adopt(remote_id: Uuid) -> Cat {
+        let mut cat = setup_cat();
+        cat.metadata.generation = Some(1);
+        cat.metadata.annotations = Some(
+            [(REMOTE_ID_ANNOTATION.to_string(), remote_id.to_string())]
+                .into_iter()
+                .collect(),
+        );
+        cat
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_adopts_the_remote_resource_referenced_by_id() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let remote_id = Uuid::new_v4();
+        let cat = Arc::new(setup_cat_to_adopt(remote_id));
+        let remote_cat = CatDto {
+            uuid: Some(remote_id),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age,
+        };
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .withf(move |id| id == remote_id.to_string())
+            .times(2)
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_create_cat().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client
+            .expect_add_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_update_status()
+            .withf(move |cat: &Cat| {
+                let status = cat.status.as_ref().unwrap();
+                status.uuid == Some(remote_id.to_string()) && status.observed_generation == Some(1)
+            })
+            .times(2)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Adopted")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_adopts_remote_values_after_adopting_by_id_without_a_conflict() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let remote_id = Uuid::new_v4();
+        let mut cat = setup_cat_to_adopt(remote_id);
+        cat.metadata.resource_version = Some("0".to_string());
+        cat.metadata.annotations.as_mut().unwrap().insert(
+            DRIFT_POLICY_ANNOTATION.to_string(),
+            "adopt-remote".to_string(),
+        );
+        let cat = Arc::new(cat);
+        let remote_cat = CatDto {
+            uuid: Some(remote_id),
+            name: cat.spec.name.clone(),
+            breed: cat.spec.breed.clone(),
+            age: cat.spec.age + 1,
+        };
+        let remote_age = remote_cat.age;
+        // Every write bumps the resource version, like the API server does.
+        let resource_version = Arc::new(AtomicUsize::new(0));
+        let bump = {
+            let resource_version = resource_version.clone();
+            move |cat: &mut Cat| {
+                let bumped = resource_version.fetch_add(1, Ordering::SeqCst) + 1;
+                cat.metadata.resource_version = Some(bumped.to_string());
+                Ok(())
+            }
+        };
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .returning(move |_| Ok(remote_cat.clone()));
+        mock_cats_api.expect_create_cat().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client.expect_add_finalizer().returning(bump.clone());
+        kube_client.expect_update_status().returning(bump);
+        let current_version = resource_version.clone();
+        kube_client
+            .expect_patch()
+            .withf(move |_, _, patch| {
+                let current = current_version.load(Ordering::SeqCst).to_string();
+                matches!(patch, Patch::Merge(patch)
+                    if patch["metadata"]["resourceVersion"] == current.as_str())
+            })
+            .times(1)
+            .returning(move |cat, _, _| {
+                let mut cat = cat.clone();
+                cat.spec.age = remote_age;
+                cat.metadata.generation = Some(2);
+                Ok(cat)
+            });
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_create_does_not_create_when_the_resource_to_adopt_is_missing() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let mut cat = setup_cat_to_adopt(Uuid::new_v4());
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(|_| {
+                Err(Error::ResponseError(ResponseContent {
+                    status: reqwest::StatusCode::NOT_FOUND,
+                    content: "Not Found".to_string(),
+                    entity: None,
+                }))
+            });
+        mock_cats_api.expect_create_cat().times(0);
+        kube_client.expect_add_finalizer().times(0);
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "AdoptFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = handle_create(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert_eq!(result.unwrap_err().class(), ErrorClass::NotFound);
+        assert_eq!(cat.status.unwrap().uuid, None);
+    }
+
+    fn setup_deleted_cat(policy: &str) -> (Cat, Uuid) {
+        let uuid = Uuid::new_v4();
+        let mut cat = setup_cat();
+        cat.metadata.annotations = Some(
+            [(DELETION_POLICY_ANNOTATION.to_string(), policy.to_string())]
+                .into_iter()
+                .collect(),
+        );
+        cat.status = Some(CatStatus {
+            uuid: Some(uuid.to_string()),
+            ..Default::default()
+        });
+        (cat, uuid)
+    }
+
+    #[tokio::test]
+    async fn test_handle_delete_retains_the_remote_resource_with_retain_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Retain");
+
+        mock_cats_api.expect_delete_cat_by_id().times(0);
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client
+            .expect_record_released()
+            .withf(move |id, reason| id == uuid.to_string() && reason == "Retain")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        kube_client
+            .expect_remove_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Retained")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result =
+            handle_delete(&kube_client, cats_api.as_ref(), &mut cat, &uuid.to_string()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_delete_leaves_the_remote_resource_with_orphan_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Orphan");
+
+        mock_cats_api.expect_delete_cat_by_id().times(0);
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        kube_client
+            .expect_record_released()
+            .withf(move |id, reason| id == uuid.to_string() && reason == "Orphan")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        kube_client
+            .expect_remove_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Orphaned")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result =
+            handle_delete(&kube_client, cats_api.as_ref(), &mut cat, &uuid.to_string()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_delete_keeps_the_finalizer_when_the_release_is_not_recorded() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Retain");
+
+        kube_client
+            .expect_record_released()
+            .times(1)
+            .returning(|_, _| {
+                Err(OperatorError::FailedToPatchResource(anyhow::anyhow!(
+                    "forbidden"
+                )))
+            });
+        kube_client.expect_remove_finalizer().times(0);
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "RetainFailed")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result =
+            handle_delete(&kube_client, cats_api.as_ref(), &mut cat, &uuid.to_string()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_delete_keeps_the_finalizer_with_an_invalid_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Keep");
+
+        mock_cats_api.expect_delete_cat_by_id().times(0);
+        kube_client.expect_remove_finalizer().times(0);
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| {
+                event.type_ == EventType::Warning && event.reason == "InvalidDeletionPolicy"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result =
+            handle_delete(&kube_client, cats_api.as_ref(), &mut cat, &uuid.to_string()).await;
+
+        assert_eq!(result.unwrap_err().class(), ErrorClass::Permanent);
+    }
+
+    fn not_found<T>() -> Error<T> {
+        Error::ResponseError(ResponseContent {
+            status: reqwest::StatusCode::NOT_FOUND,
+            content: "Not Found".to_string(),
+            entity: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_handle_delete_removes_the_finalizer_when_the_remote_resource_is_gone() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Delete");
+
+        mock_cats_api
+            .expect_delete_cat_by_id()
+            .times(1)
+            .returning(|_| Err(not_found()));
+        kube_client
+            .expect_remove_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "AlreadyDeleted")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result =
+            handle_delete(&kube_client, cats_api.as_ref(), &mut cat, &uuid.to_string()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_recreates_a_vanished_remote_resource() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, _) = setup_drifted_cat(None);
+        let new_uuid = Uuid::new_v4();
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(|_| Err(not_found()));
+        mock_cats_api
+            .expect_create_cat()
+            .withf(|dto| dto.uuid.is_none())
+            .times(1)
+            .returning(move |dto| {
+                Ok(CatDto {
+                    uuid: Some(new_uuid),
+                    ..dto
+                })
+            });
+        kube_client
+            .expect_update_status()
+            .withf(move |cat| {
+                cat.status.as_ref().and_then(|status| status.uuid.clone())
+                    == Some(new_uuid.to_string())
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Normal && event.reason == "Recreated")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_for_drift_marks_a_vanished_remote_resource_lost_with_report_only_policy() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, _) = setup_drifted_cat(Some("report-only"));
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(|_| Err(not_found()));
+        mock_cats_api.expect_create_cat().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat| {
+                cat.status.as_ref().is_some_and(|status| {
+                    status.conditions.iter().any(|condition| {
+                        condition.type_ == LOST_CONDITION && condition.status == "True"
+                    })
+                })
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.type_ == EventType::Warning && event.reason == "RemoteLost")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let result = check_for_drift(&kube_client, cats_api.as_ref(), &mut cat).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_does_not_mark_a_lost_resource_synced() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (cat, _) = setup_drifted_cat(Some("report-only"));
+        let cat = Arc::new(cat);
+
+        mock_cats_api
+            .expect_get_cat_by_id()
+            .times(1)
+            .returning(|_| Err(not_found()));
+        mock_cats_api.expect_create_cat().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat| {
+                let status = cat.status.as_ref().unwrap();
+                status.sync.last_sync_time.is_none()
+                    && !status.conditions.iter().any(|condition| {
+                        condition.type_ == SYNCED_CONDITION && condition.status == "True"
+                    })
+            })
+            .times(1..)
+            .returning(|_| Ok(()));
+        kube_client.expect_publish_event().returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+
+        let result = reconcile(
+            Arc::clone(&cat),
+            Arc::new(ContextData::new(kube_client.clone(), cats_api.clone())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_leaves_a_suspended_resource_alone() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, _) = setup_drifted_cat(None);
+        cat.metadata.generation = Some(2);
+        cat.metadata.annotations = Some(
+            [(SUSPEND_ANNOTATION.to_string(), "true".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        mock_cats_api.expect_create_cat().times(0);
+        mock_cats_api.expect_update_cat_by_id().times(0);
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        kube_client
+            .expect_update_status()
+            .withf(|cat| {
+                cat.status.as_ref().is_some_and(|status| {
+                    status.conditions.iter().any(|condition| {
+                        condition.type_ == SUSPENDED_CONDITION && condition.status == "True"
+                    })
+                })
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.reason == "Suspended")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+        let result = reconcile(
+            Arc::new(cat),
+            Arc::new(ContextData::new(kube_client, cats_api)),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_deletes_a_suspended_resource() {
+        let mut kube_client = MockKubeApiClient::new();
+        let mut mock_cats_api = MockCatsApi::new();
+        let (mut cat, uuid) = setup_deleted_cat("Delete");
+        cat.metadata
+            .annotations
+            .get_or_insert_with(Default::default)
+            .insert(SUSPEND_ANNOTATION.to_string(), "true".to_string());
+        cat.metadata.deletion_timestamp = Some(Time(chrono::Utc::now()));
+
+        mock_cats_api
+            .expect_delete_cat_by_id()
+            .withf(move |id| id == uuid.to_string())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_cats_api.expect_get_cat_by_id().times(0);
+        kube_client
+            .expect_remove_finalizer()
+            .times(1)
+            .returning(|_| Ok(()));
+        kube_client
+            .expect_publish_event()
+            .withf(|_, event| event.reason == "Deleted")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let cats_api = Arc::new(mock_cats_api) as Arc<dyn CatsApi>;
+        let kube_client = Arc::new(kube_client) as Arc<dyn KubeApi<Cat>>;
+        let result = reconcile(
+            Arc::new(cat),
+            Arc::new(ContextData::new(kube_client, cats_api)),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+    use openapi::apis::cats_api::{
+        CreateCatError, DeleteCatByIdError, GetCatByIdError, GetCatsError, UpdateCatByIdError,
+    };
+    use openapi::{apis::cats_api::CatsApi, apis::Error, models::Cat as CatDto};
+    use operator::{
+        cli::ConfigOverrides,
+        config::OperatorConfig,
+        controllers::reconciler::RemoteResource,
+        reload::{watch_config, Reloadable},
+        types::cat::Cat,
+    };
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use tokio_util::sync::CancellationToken;
+
+    mock! {
+        pub CatsApi {}
+
+        #[async_trait]
+        impl CatsApi for CatsApi {
+            async fn create_cat<'cat>(&self, cat: CatDto) -> Result<CatDto, Error<CreateCatError>>;
+            async fn delete_cat_by_id<'id>(&self, id: &'id str) -> Result<(), Error<DeleteCatByIdError>>;
+            async fn get_cat_by_id<'id>(&self, id: &'id str) -> Result<CatDto, Error<GetCatByIdError>>;
+            async fn update_cat_by_id<'id, 'cat>(&self, id: &'id str, cat: CatDto) -> Result<CatDto, Error<UpdateCatByIdError>>;
+            async fn get_cats(&self) -> Result<Vec<CatDto>, Error<GetCatsError>>;
+        }
+    }
+
+    fn cats_api_returning(name: &str) -> Arc<dyn CatsApi> {
+        let name = name.to_string();
+        let mut cats_api = MockCatsApi::new();
+        cats_api.expect_get_cat_by_id().returning(move |_| {
+            Ok(CatDto {
+                uuid: None,
+                name: name.clone(),
+                breed: "Siamese".to_string(),
+                age: 3,
+            })
+        });
+        Arc::new(cats_api)
+    }
+
+    #[test]
+    fn test_reloadable_returns_the_latest_value() {
+        let reloadable = Reloadable::new(Arc::new(1));
+        let previous = reloadable.get();
+
+        reloadable.set(Arc::new(2));
+
+        assert_eq!(*previous, 1);
+        assert_eq!(*reloadable.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reloadable_client_calls_the_latest_client() {
+        let cats_client: Reloadable<dyn CatsApi> = Reloadable::new(cats_api_returning("Whiskers"));
+
+        let before = RemoteResource::<Cat>::get_by_id(&cats_client, "1")
+            .await
+            .unwrap();
+        cats_client.set(cats_api_returning("Tom"));
+        let after = RemoteResource::<Cat>::get_by_id(&cats_client, "1")
+            .await
+            .unwrap();
+
+        assert_eq!(before.name, "Whiskers");
+        assert_eq!(after.name, "Tom");
+    }
+
+    #[tokio::test]
+    async fn test_watch_config_applies_changed_and_ignores_invalid_files() {
+        let path = std::env::temp_dir().join(format!("operator-{}.yaml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "requeue:\n  intervalSeconds: 30\n").unwrap();
+        let overrides = ConfigOverrides {
+            config_file: Some(path.clone()),
+            ..Default::default()
+        };
+        let current = overrides.load().unwrap();
+        let applied = Arc::new(Mutex::new(Vec::<OperatorConfig>::new()));
+        let shutdown = CancellationToken::new();
+
+        let watcher = {
+            let applied = applied.clone();
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                watch_config(
+                    &overrides,
+                    current,
+                    Duration::from_millis(10),
+                    shutdown,
+                    |config| applied.lock().unwrap().push(config.clone()),
+                )
+                .await;
+            })
+        };
+
+        std::fs::write(&path, "requeue:\n  intervalSeconds: 0\n").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        std::fs::write(&path, "requeue:\n  intervalSeconds: 60\n").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown.cancel();
+        watcher.await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let applied = applied.lock().unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].requeue.interval_seconds, 60);
+    }
+}
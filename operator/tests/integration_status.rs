@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+    use operator::{
+        errors::OperatorError,
+        types::{
+            cat::CatStatus,
+            dog::DogStatus,
+            horse::HorseStatus,
+            status::{DriftReport, FieldDrift, ResourceStatus, SyncStatus},
+        },
+    };
+    use serde::Serialize;
+
+    fn condition(type_: &str, status: &str, reason: &str, hour: u32) -> Condition {
+        Condition {
+            type_: type_.to_string(),
+            status: status.to_string(),
+            reason: reason.to_string(),
+            message: format!("{} is {}", type_, status),
+            observed_generation: Some(1),
+            last_transition_time: Time(Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap()),
+        }
+    }
+
+    fn assert_keeps_conditions_unique<S: ResourceStatus>() {
+        let mut status = S::default();
+
+        status.set_condition(condition("Ready", "False", "Reconciling", 1));
+        status.set_condition(condition("Synced", "True", "Created", 1));
+        status.set_condition(condition("Ready", "True", "Synced", 2));
+
+        assert_eq!(status.conditions().len(), 2);
+        assert_eq!(status.condition("Ready").unwrap().status, "True");
+        assert_eq!(status.condition("Synced").unwrap().reason, "Created");
+        assert!(status.condition("Error").is_none());
+    }
+
+    fn assert_keeps_transition_time_while_status_is_unchanged<S: ResourceStatus>() {
+        let mut status = S::default();
+        status.set_condition(condition("Ready", "True", "Synced", 1));
+
+        status.set_condition(condition("Ready", "True", "Adopted", 2));
+
+        let ready = status.condition("Ready").unwrap();
+        assert_eq!(ready.reason, "Adopted");
+        assert_eq!(
+            ready.last_transition_time,
+            condition("Ready", "True", "Synced", 1).last_transition_time
+        );
+    }
+
+    fn assert_updates_transition_time_when_status_changes<S: ResourceStatus>() {
+        let mut status = S::default();
+        status.set_condition(condition("Ready", "True", "Synced", 1));
+
+        status.set_condition(condition("Ready", "False", "Drifted", 2));
+
+        assert_eq!(
+            status.condition("Ready").unwrap().last_transition_time,
+            condition("Ready", "False", "Drifted", 2).last_transition_time
+        );
+    }
+
+    fn assert_removes_conditions<S: ResourceStatus>() {
+        let mut status = S::default();
+        status.set_condition(condition("Ready", "True", "Synced", 1));
+        status.set_condition(condition("Drifted", "False", "InSync", 1));
+
+        let removed = status.remove_condition("Drifted");
+
+        assert_eq!(removed.unwrap().reason, "InSync");
+        assert!(status.remove_condition("Drifted").is_none());
+        assert_eq!(status.conditions().len(), 1);
+    }
+
+    fn assert_tracks_observed_generation<S: ResourceStatus>() {
+        let mut status = S::default();
+        assert!(!status.is_observed(Some(1)));
+
+        status.set_observed_generation(Some(1));
+
+        assert!(status.is_observed(Some(1)));
+        assert!(!status.is_observed(Some(2)));
+    }
+
+    #[test]
+    fn test_set_condition_keeps_condition_types_unique() {
+        assert_keeps_conditions_unique::<CatStatus>();
+        assert_keeps_conditions_unique::<DogStatus>();
+        assert_keeps_conditions_unique::<HorseStatus>();
+    }
+
+    #[test]
+    fn test_set_condition_keeps_transition_time_while_status_is_unchanged() {
+        assert_keeps_transition_time_while_status_is_unchanged::<CatStatus>();
+        assert_keeps_transition_time_while_status_is_unchanged::<DogStatus>();
+        assert_keeps_transition_time_while_status_is_unchanged::<HorseStatus>();
+    }
+
+    #[test]
+    fn test_set_condition_updates_transition_time_when_status_changes() {
+        assert_updates_transition_time_when_status_changes::<CatStatus>();
+        assert_updates_transition_time_when_status_changes::<DogStatus>();
+        assert_updates_transition_time_when_status_changes::<HorseStatus>();
+    }
+
+    #[test]
+    fn test_remove_condition() {
+        assert_removes_conditions::<CatStatus>();
+        assert_removes_conditions::<DogStatus>();
+        assert_removes_conditions::<HorseStatus>();
+    }
+
+    #[test]
+    fn test_is_observed_compares_the_observed_generation() {
+        assert_tracks_observed_generation::<CatStatus>();
+        assert_tracks_observed_generation::<DogStatus>();
+        assert_tracks_observed_generation::<HorseStatus>();
+    }
+
+    fn time(hour: u32) -> Time {
+        Time(Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn test_sync_status_counts_consecutive_failures() {
+        let mut sync = SyncStatus::default();
+        let error = OperatorError::InvalidResource("uuid is empty".to_string());
+
+        sync.failed(time(1), &error);
+        sync.failed(time(2), &error);
+
+        assert_eq!(sync.consecutive_failures, 2);
+        assert_eq!(sync.last_attempt_time, Some(time(2)));
+        assert_eq!(sync.last_error_message, Some(error.to_string()));
+        assert_eq!(sync.last_error_code, None);
+        assert_eq!(sync.last_sync_time, None);
+    }
+
+    #[test]
+    fn test_sync_status_clears_the_error_once_synced() {
+        let mut sync = SyncStatus::default();
+        sync.failed(
+            time(1),
+            &OperatorError::InvalidResource("uuid is empty".to_string()),
+        );
+
+        sync.succeeded(time(2));
+
+        assert_eq!(
+            sync,
+            SyncStatus {
+                last_sync_time: Some(time(2)),
+                last_attempt_time: Some(time(2)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_sync_status_clears_the_error_in_a_merge_patch() {
+        let status = serde_json::to_value(SyncStatus::default()).unwrap();
+
+        assert_eq!(status["lastErrorMessage"], serde_json::Value::Null);
+        assert_eq!(status["lastErrorCode"], serde_json::Value::Null);
+        assert!(status.get("lastErrorMessage").is_some());
+    }
+
+    #[derive(Serialize)]
+    struct Spec {
+        name: String,
+        age: i32,
+        owner: Owner,
+    }
+
+    #[derive(Serialize)]
+    struct Owner {
+        name: String,
+        phone: Option<String>,
+    }
+
+    fn spec(age: i32, owner: &str, phone: Option<&str>) -> Spec {
+        Spec {
+            name: "Whiskers".to_string(),
+            age,
+            owner: Owner {
+                name: owner.to_string(),
+                phone: phone.map(str::to_string),
+            },
+        }
+    }
+
+    #[test]
+    fn test_drift_report_lists_the_fields_that_differ() {
+        let report = DriftReport::between(
+            &spec(3, "Alice", None),
+            &spec(4, "Bob", Some("555")),
+            "enforce",
+            time(1),
+        );
+
+        assert_eq!(report.detected_at, Some(time(1)));
+        assert_eq!(report.policy, "enforce");
+        assert!(!report.corrected);
+        assert_eq!(
+            report.fields,
+            vec![
+                FieldDrift {
+                    field: "age".to_string(),
+                    desired: "3".to_string(),
+                    remote: "4".to_string(),
+                },
+                FieldDrift {
+                    field: "owner.name".to_string(),
+                    desired: "\"Alice\"".to_string(),
+                    remote: "\"Bob\"".to_string(),
+                },
+                FieldDrift {
+                    field: "owner.phone".to_string(),
+                    desired: "null".to_string(),
+                    remote: "\"555\"".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            report.summary(),
+            "age (desired 3, remote 4), owner.name (desired \"Alice\", remote \"Bob\"), \
+             owner.phone (desired null, remote \"555\")"
+        );
+    }
+
+    #[test]
+    fn test_drift_report_of_equal_specs_is_empty() {
+        let report = DriftReport::between(
+            &spec(3, "Alice", None),
+            &spec(3, "Alice", None),
+            "enforce",
+            time(1),
+        );
+
+        assert!(report.fields.is_empty());
+    }
+
+    #[test]
+    fn test_drift_reports_are_the_same_drift_regardless_of_when_they_were_detected() {
+        let first = DriftReport::between(
+            &spec(3, "Alice", None),
+            &spec(4, "Alice", None),
+            "report-only",
+            time(1),
+        );
+        let again = DriftReport::between(
+            &spec(3, "Alice", None),
+            &spec(4, "Alice", None),
+            "report-only",
+            time(2),
+        );
+        let other = DriftReport::between(
+            &spec(3, "Alice", None),
+            &spec(5, "Alice", None),
+            "report-only",
+            time(2),
+        );
+
+        assert!(first.same_drift(&again));
+        assert!(!first.same_drift(&other));
+    }
+}
@@ -16,6 +16,8 @@ mod test {
     use serial_test::serial;
     use std::{collections::BTreeMap, result::Result, time::Duration};
 
+    const OTHER_NAMESPACE: &str = "e2e-pets";
+
     fn get_default_config() -> Option<ConfigMap> {
         Some(ConfigMap {
             metadata: ObjectMeta {
@@ -33,6 +35,15 @@ mod test {
         })
     }
 
+    fn get_multi_namespace_config() -> Option<ConfigMap> {
+        let mut config = get_default_config()?;
+        config.data.get_or_insert_with(BTreeMap::new).insert(
+            "WATCH_NAMESPACES".to_string(),
+            format!("default,{}", OTHER_NAMESPACE),
+        );
+        Some(config)
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_crds_exist() -> anyhow::Result<(), anyhow::Error> {
@@ -76,7 +87,7 @@ mod test {
         operator.package("localhost:5005").await?;
         operator.deploy_on(&cluster).await?;
 
-        let api: Api<Cat> = client::setup().await?;
+        let api: Api<Cat> = client::setup("default").await?;
         let resource = Cat {
             metadata: ObjectMeta {
                 name: Some("test-cat".to_string()),
@@ -119,4 +130,61 @@ mod test {
 
         anyhow::Ok(())
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_add_finalizer_outside_the_default_namespace() -> anyhow::Result<(), anyhow::Error>
+    {
+        let cluster = cluster::setup().await?;
+        cluster::setup_namespace(OTHER_NAMESPACE).await?;
+
+        let fake_api = FakeApi::new();
+        fake_api.package("localhost:5005").await?;
+        fake_api.deploy_on(&cluster).await?;
+
+        let operator = Operator::new(get_multi_namespace_config());
+        operator.package("localhost:5005").await?;
+        operator.deploy_on(&cluster).await?;
+
+        let api: Api<Cat> = client::setup(OTHER_NAMESPACE).await?;
+        let resource = Cat {
+            metadata: ObjectMeta {
+                name: Some("test-cat".to_string()),
+                namespace: Some(OTHER_NAMESPACE.to_string()),
+                ..Default::default()
+            },
+            spec: CatSpec {
+                name: "test-cat".to_string(),
+                age: 1,
+                breed: "test".to_string(),
+            },
+            status: None,
+        };
+
+        // apply the resource
+        match api.get("test-cat").await {
+            Result::Ok(_) => {}
+            Err(_) => {
+                api.create(&Default::default(), &resource).await?;
+            }
+        }
+
+        // wait for the resource to be created
+        api.wait_for_field("test-cat", "$.metadata.finalizers", Duration::from_secs(30))
+            .await?;
+
+        // get the resource
+        let cat = api.get("test-cat").await?;
+
+        // check if the finalizer is added in the non-default namespace
+        assert_eq!(
+            cat.metadata.finalizers,
+            Some(vec!["finalizers.example.com".to_string()])
+        );
+
+        operator.undeploy_from(&cluster).await?;
+        fake_api.undeploy_from(&cluster).await?;
+
+        anyhow::Ok(())
+    }
 }
@@ -24,19 +24,19 @@ pub async fn setup_crd() -> Result<Api<CustomResourceDefinition>> {
 }
 
 /// Sets up a Kubernetes API client for managing resources of type `T`.
-/// The client is namespaced to the `default` namespace.
+/// The client is namespaced to the given `namespace`, which must be one the operator watches.
 /// The resource must implement the `Resource`, `Clone`, `DeserializeOwned`, `Serialize`, `Debug`, and `HasStatus` traits.
 /// The resource's `DynamicType` must implement the `Default` trait.
 /// The resource must have a `NamespaceResourceScope`.
 /// The resource must have a `Status` field.
-pub async fn setup<T>() -> Result<Api<T>>
+pub async fn setup<T>(namespace: &str) -> Result<Api<T>>
 where
     T: Resource + Clone + DeserializeOwned + Serialize + Debug + HasStatus,
     <T as Resource>::DynamicType: Default,
     T: Resource<Scope = NamespaceResourceScope>,
 {
     let client = Client::try_default().await?;
-    let api: Api<T> = Api::namespaced(client, "default");
+    let api: Api<T> = Api::namespaced(client, namespace);
     Ok(api)
 }
 
@@ -69,7 +69,7 @@ pub trait Waiter {
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
-    ///     let api: Api<Cat> = client::setup::<Cat>().await?;
+    ///     let api: Api<Cat> = client::setup::<Cat>("default").await?;
     ///     api.wait_for_field("test-cat", "$.metadata.finalizers", Duration::from_secs(30)).await?;
     ///     let cat = api.get("test-cat").await?;
     ///     assert_eq!(cat.metadata.finalizers, Some(vec!["test-finalizer".to_string()]));
@@ -111,7 +111,7 @@ where
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
-    ///     let api: Api<Cat> = client::setup::<Cat>().await?;
+    ///     let api: Api<Cat> = client::setup::<Cat>("default").await?;
     ///     api.wait_for_field("test-cat", "$.metadata.finalizers", Duration::from_secs(30)).await?;
     ///     let cat = api.get("test-cat").await?;
     ///     assert_eq!(cat.metadata.finalizers, Some(vec!["test-finalizer".to_string()]));
@@ -11,6 +11,45 @@ pub async fn setup() -> anyhow::Result<String> {
     Ok("k3d-k3s-default".to_string())
 }
 
+/// Creates `namespace` if it does not exist yet and grants the operator access to it
+/// by applying the namespaced role and rolebinding there.
+pub async fn setup_namespace(namespace: &str) -> anyhow::Result<()> {
+    let root_dir = Path::new("..");
+    let exists = tokio::process::Command::new("kubectl")
+        .args(["get", "namespace", namespace])
+        .current_dir(root_dir)
+        .status()
+        .await?
+        .success();
+    if !exists {
+        tokio::process::Command::new("kubectl")
+            .args(["create", "namespace", namespace])
+            .current_dir(root_dir)
+            .status()
+            .await?
+            .success()
+            .then_some(())
+            .ok_or_else(|| anyhow::anyhow!("`kubectl create namespace {}` failed", namespace))?;
+    }
+    tokio::process::Command::new("kubectl")
+        .args([
+            "apply",
+            "-n",
+            namespace,
+            "-f",
+            "manifests/rbac/role.yaml",
+            "-f",
+            "manifests/rbac/rolebinding.yaml",
+        ])
+        .current_dir(root_dir)
+        .status()
+        .await?
+        .success()
+        .then_some(())
+        .ok_or_else(|| anyhow::anyhow!("`kubectl apply` of the rbac in {} failed", namespace))?;
+    Ok(())
+}
+
 pub async fn teardown() -> anyhow::Result<()> {
     let root_dir = Path::new("..");
     let _ = tokio::process::Command::new("task")